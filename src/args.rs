@@ -1,23 +1,486 @@
-use crate::meta;
+use anyhow::{Context, bail};
+use bip39::Language;
+use bitcoin::Network;
 use console::style;
+use seedctl::{
+  entropy::{DiceScheme, EntropySource},
+  export::ExportFormat,
+  meta, mnemonic,
+  offline::OfflinePolicy,
+  seedqr::SeedQrFormat,
+  sheet::SheetMode,
+  slip39::SharingSpec,
+  wallet::{self, AddressChain, ScriptType},
+};
+use std::{ops::RangeInclusive, path::PathBuf};
+use zeroize::Zeroizing;
+
+use crate::viewer::DisplayMode;
+
 pub enum CliAction {
   Version,
   About,
-  Run,
+  Run(Box<RunOptions>),
+  Decrypt(DecryptOptions),
+}
+
+/// `seedctl decrypt <FILE>`: opens an encrypted export.
+pub struct DecryptOptions {
+  pub input: PathBuf,
+  /// `-` writes to standard output; `None` drops the `.age` extension.
+  pub output: Option<PathBuf>,
+  pub password_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DiceMode {
+  Auto,
+  Manual,
+}
+
+/// Values supplied on the command line. Every `None` falls back to the
+/// interactive prompt, unless `--yes` is given, in which case the prompt's
+/// default is used instead.
+#[derive(Default)]
+pub struct RunOptions {
+  pub bits: Option<usize>,
+  pub dice: Option<Zeroizing<Vec<u8>>>,
+  pub dice_mode: Option<DiceMode>,
+  pub dice_source: Option<EntropySource>,
+  pub dice_scheme: Option<DiceScheme>,
+  pub language: Option<Language>,
+  pub network: Option<Network>,
+  pub script_type: Option<ScriptType>,
+  pub accounts: Option<RangeInclusive<u32>>,
+  pub address_start: Option<u32>,
+  pub address_count: Option<u32>,
+  pub address_chain: Option<AddressChain>,
+  pub mnemonic_file: Option<PathBuf>,
+  pub slip39: Option<SharingSpec>,
+  pub slip39_shares: Option<PathBuf>,
+  pub slip39_passphrase_file: Option<PathBuf>,
+  pub seedqr: Option<SeedQrFormat>,
+  pub seedqr_file: Option<PathBuf>,
+  pub display: Option<DisplayMode>,
+  pub passphrase_file: Option<PathBuf>,
+  pub exports: Vec<(ExportFormat, PathBuf)>,
+  pub backup_sheet: Option<PathBuf>,
+  pub backup_sheet_mode: Option<SheetMode>,
+  pub encrypt_password_file: Option<PathBuf>,
+  pub offline: OfflinePolicy,
+  pub yes: bool,
 }
 
-pub fn parse_args() -> CliAction {
-  let args: Vec<String> = std::env::args().collect();
+pub fn parse_args() -> anyhow::Result<CliAction> {
+  let args: Vec<String> = std::env::args().skip(1).collect();
 
   if args.iter().any(|a| a == "--version" || a == "-V") {
-    CliAction::Version
-  } else if args.iter().any(|a| a == "--about" || a == "--help") {
-    CliAction::About
-  } else {
-    CliAction::Run
+    return Ok(CliAction::Version);
+  } else if args
+    .iter()
+    .any(|a| a == "--about" || a == "--help" || a == "-h")
+  {
+    return Ok(CliAction::About);
+  }
+
+  if args.first().is_some_and(|a| a == "decrypt") {
+    return parse_decrypt_args(&args[1..]).map(CliAction::Decrypt);
+  }
+
+  let mut opts = RunOptions::default();
+  let mut dice: Option<Zeroizing<String>> = None;
+  let mut iter = args.into_iter();
+
+  while let Some(arg) = iter.next() {
+    // Accept both "--flag value" and "--flag=value"
+    let (flag, inline) = match arg.split_once('=') {
+      Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+      _ => (arg, None),
+    };
+
+    if flag == "--yes" || flag == "-y" {
+      opts.yes = true;
+      continue;
+    }
+    if flag == "--require-offline" {
+      opts.offline = OfflinePolicy::Require;
+      continue;
+    }
+
+    let value = match inline.or_else(|| iter.next()) {
+      Some(v) => v,
+      None => bail!("Missing value for {}", flag),
+    };
+
+    match flag.as_str() {
+      "--offline" => opts.offline = parse_offline(&value)?,
+      "--words" => opts.bits = Some(parse_words(&value)?),
+      "--dice" => dice = Some(Zeroizing::new(value)),
+      "--dice-mode" => opts.dice_mode = Some(parse_dice_mode(&value)?),
+      "--dice-source" => opts.dice_source = Some(parse_dice_source(&value)?),
+      "--dice-scheme" => opts.dice_scheme = Some(parse_dice_scheme(&value)?),
+      "--language" => opts.language = Some(parse_language(&value)?),
+      "--network" => opts.network = Some(parse_network(&value)?),
+      "--script-type" => opts.script_type = Some(parse_script_type(&value)?),
+      "--account" => opts.accounts = Some(parse_account_range(&value)?),
+      "--address-start" => opts.address_start = Some(parse_number(&flag, &value)?),
+      "--address-count" => opts.address_count = Some(parse_address_count(&value)?),
+      "--address-chain" => opts.address_chain = Some(parse_address_chain(&value)?),
+      "--mnemonic-file" => opts.mnemonic_file = Some(PathBuf::from(value)),
+      "--slip39" => {
+        opts.slip39 = Some(
+          SharingSpec::parse(&value)
+            .with_context(|| format!("Invalid --slip39 value '{}'", value))?,
+        )
+      }
+      "--slip39-shares" => opts.slip39_shares = Some(PathBuf::from(value)),
+      "--slip39-passphrase-file" => opts.slip39_passphrase_file = Some(PathBuf::from(value)),
+      "--seedqr" => opts.seedqr = Some(parse_seedqr(&value)?),
+      "--seedqr-file" => opts.seedqr_file = Some(PathBuf::from(value)),
+      "--display" => opts.display = Some(parse_display(&value)?),
+      "--passphrase-file" => opts.passphrase_file = Some(PathBuf::from(value)),
+      "--export" => opts
+        .exports
+        .push((ExportFormat::Seedctl, PathBuf::from(value))),
+      "--export-private" => opts
+        .exports
+        .push((ExportFormat::SeedctlPrivate, PathBuf::from(value))),
+      "--export-core" => opts
+        .exports
+        .push((ExportFormat::BitcoinCore, PathBuf::from(value))),
+      "--export-electrum" => opts
+        .exports
+        .push((ExportFormat::Electrum, PathBuf::from(value))),
+      "--export-sparrow" => opts
+        .exports
+        .push((ExportFormat::Sparrow, PathBuf::from(value))),
+      "--encrypt-password-file" => opts.encrypt_password_file = Some(PathBuf::from(value)),
+      "--backup-sheet" => opts.backup_sheet = Some(PathBuf::from(value)),
+      "--backup-sheet-mode" => opts.backup_sheet_mode = Some(parse_sheet_mode(&value)?),
+      "--export-addresses" => {
+        let path = PathBuf::from(value);
+        let format = match path.extension() {
+          Some(ext) if ext.eq_ignore_ascii_case("csv") => ExportFormat::AddressesCsv,
+          _ => ExportFormat::AddressesJson,
+        };
+        opts.exports.push((format, path))
+      }
+      _ => bail!("Unknown option: {} (see --help)", flag),
+    }
+  }
+
+  // Rolls are parsed once the source is known; --dice alone means d6
+  if let Some(dice) = dice {
+    let source = opts.dice_source.unwrap_or(EntropySource::D6);
+    opts.dice = Some(
+      source
+        .parse_rolls(&dice)
+        .map_err(|e| anyhow::anyhow!("Invalid --dice value: {}", e))?,
+    );
+  }
+
+  if let (Some(scheme), Some(source)) = (opts.dice_scheme, opts.dice_source)
+    && !scheme.supports(source)
+  {
+    bail!("--dice-scheme {} only supports d6 rolls", scheme.name());
+  }
+
+  // A dice sequence on the command line is only meaningful in manual mode
+  match (opts.dice.is_some(), opts.dice_mode) {
+    (true, None) => opts.dice_mode = Some(DiceMode::Manual),
+    (true, Some(DiceMode::Auto)) => bail!("--dice cannot be combined with --dice-mode auto"),
+    _ => {}
+  }
+
+  if opts.mnemonic_file.is_some() && opts.uses_dice() {
+    bail!("--mnemonic-file cannot be combined with --words or the --dice options");
+  }
+
+  // Writing a SeedQR file alone means the standard format
+  if opts.seedqr_file.is_some() && opts.seedqr.is_none() {
+    opts.seedqr = Some(SeedQrFormat::Standard);
+  }
+
+  if opts.slip39_shares.is_some() && (opts.mnemonic_file.is_some() || opts.uses_dice()) {
+    bail!("--slip39-shares cannot be combined with --mnemonic-file, --words or the --dice options");
+  }
+  if opts.slip39_passphrase_file.is_some() && opts.slip39_shares.is_none() {
+    bail!("--slip39-passphrase-file requires --slip39-shares");
+  }
+  // A recovered SLIP-39 master secret has no mnemonic to show or protect
+  if opts.slip39_shares.is_some() {
+    if opts.passphrase_file.is_some() {
+      bail!(
+        "--passphrase-file cannot be combined with --slip39-shares; use --slip39-passphrase-file"
+      );
+    }
+    if opts.seedqr.is_some() {
+      bail!("--seedqr and --seedqr-file cannot be combined with --slip39-shares");
+    }
+    if matches!(opts.backup_sheet_mode, Some(mode) if mode != SheetMode::Public) {
+      bail!("--slip39-shares only allows --backup-sheet-mode public");
+    }
+  }
+
+  Ok(CliAction::Run(Box::new(opts)))
+}
+
+fn parse_decrypt_args(args: &[String]) -> anyhow::Result<DecryptOptions> {
+  let mut input = None;
+  let mut output = None;
+  let mut password_file = None;
+  let mut iter = args.iter().cloned();
+
+  while let Some(arg) = iter.next() {
+    let (flag, inline) = match arg.split_once('=') {
+      Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+      _ => (arg, None),
+    };
+    if !flag.starts_with("--") {
+      if input.replace(PathBuf::from(flag)).is_some() {
+        bail!("decrypt takes a single file");
+      }
+      continue;
+    }
+
+    let value = match inline.or_else(|| iter.next()) {
+      Some(v) => v,
+      None => bail!("Missing value for {}", flag),
+    };
+    match flag.as_str() {
+      "--output" => output = Some(PathBuf::from(value)),
+      "--password-file" => password_file = Some(PathBuf::from(value)),
+      _ => bail!("Unknown decrypt option: {} (see --help)", flag),
+    }
+  }
+
+  Ok(DecryptOptions {
+    input: input.context("Missing file to decrypt: seedctl decrypt <FILE>")?,
+    output,
+    password_file,
+  })
+}
+
+impl RunOptions {
+  /// True when any dice-related flag was given, which selects the
+  /// "generate from dice" path without asking.
+  pub fn uses_dice(&self) -> bool {
+    self.bits.is_some()
+      || self.dice.is_some()
+      || self.dice_mode.is_some()
+      || self.dice_source.is_some()
+      || self.dice_scheme.is_some()
+  }
+
+  pub fn has_address_range(&self) -> bool {
+    self.address_start.is_some() || self.address_count.is_some() || self.address_chain.is_some()
+  }
+}
+
+fn parse_words(value: &str) -> anyhow::Result<usize> {
+  match value.parse::<usize>() {
+    Ok(words) if mnemonic::VALID_WORD_COUNTS.contains(&words) => Ok(mnemonic::entropy_bits(words)),
+    _ => bail!(
+      "Invalid --words value '{}': expected 12, 15, 18, 21 or 24",
+      value
+    ),
+  }
+}
+
+fn parse_dice_source(value: &str) -> anyhow::Result<EntropySource> {
+  match EntropySource::from_name(value) {
+    Some(source) => Ok(source),
+    None => bail!(
+      "Invalid --dice-source value '{}': expected d6, coin, d4, d8, d10, d12 or d20",
+      value
+    ),
+  }
+}
+
+fn parse_offline(value: &str) -> anyhow::Result<OfflinePolicy> {
+  match OfflinePolicy::from_name(value) {
+    Some(policy) => Ok(policy),
+    None => bail!(
+      "Invalid --offline value '{}': expected require, warn or off",
+      value
+    ),
+  }
+}
+
+fn parse_seedqr(value: &str) -> anyhow::Result<SeedQrFormat> {
+  match SeedQrFormat::from_name(value) {
+    Some(format) => Ok(format),
+    None => bail!(
+      "Invalid --seedqr value '{}': expected standard or compact",
+      value
+    ),
+  }
+}
+
+fn parse_display(value: &str) -> anyhow::Result<DisplayMode> {
+  match DisplayMode::from_name(value) {
+    Some(mode) => Ok(mode),
+    None => bail!(
+      "Invalid --display value '{}': expected pages, words or plain",
+      value
+    ),
+  }
+}
+
+fn parse_sheet_mode(value: &str) -> anyhow::Result<SheetMode> {
+  match SheetMode::from_name(value) {
+    Some(mode) => Ok(mode),
+    None => bail!(
+      "Invalid --backup-sheet-mode value '{}': expected full, template or public",
+      value
+    ),
+  }
+}
+
+fn parse_dice_mode(value: &str) -> anyhow::Result<DiceMode> {
+  match value {
+    "auto" => Ok(DiceMode::Auto),
+    "manual" => Ok(DiceMode::Manual),
+    _ => bail!(
+      "Invalid --dice-mode value '{}': expected manual or auto",
+      value
+    ),
+  }
+}
+
+fn parse_dice_scheme(value: &str) -> anyhow::Result<DiceScheme> {
+  match DiceScheme::from_name(value) {
+    Some(scheme) => Ok(scheme),
+    None => bail!(
+      "Invalid --dice-scheme value '{}': expected seedctl, coldcard or raw",
+      value
+    ),
+  }
+}
+
+fn parse_language(value: &str) -> anyhow::Result<Language> {
+  match mnemonic::language_from_name(value) {
+    Some(language) => Ok(language),
+    None => bail!(
+      "Invalid --language value '{}': expected {}",
+      value,
+      mnemonic::LANGUAGES
+        .iter()
+        .map(|l| mnemonic::language_name(*l))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+  }
+}
+
+fn parse_network(value: &str) -> anyhow::Result<Network> {
+  match value {
+    "bitcoin" | "mainnet" => Ok(Network::Bitcoin),
+    "testnet" => Ok(Network::Testnet),
+    _ => bail!(
+      "Invalid --network value '{}': expected bitcoin or testnet",
+      value
+    ),
+  }
+}
+
+fn parse_script_type(value: &str) -> anyhow::Result<ScriptType> {
+  match ScriptType::from_name(value) {
+    Some(script_type) => Ok(script_type),
+    None => bail!(
+      "Invalid --script-type value '{}': expected bip84, bip49, bip44 or bip86",
+      value
+    ),
+  }
+}
+
+fn parse_number(flag: &str, value: &str) -> anyhow::Result<u32> {
+  value
+    .parse()
+    .with_context(|| format!("Invalid {} value '{}': expected a number", flag, value))
+}
+
+fn parse_address_count(value: &str) -> anyhow::Result<u32> {
+  match value.parse::<u32>() {
+    Ok(count) if count <= wallet::MAX_ADDRESS_COUNT => Ok(count),
+    _ => bail!(
+      "Invalid --address-count value '{}': expected a number from 0 to {}",
+      value,
+      wallet::MAX_ADDRESS_COUNT
+    ),
+  }
+}
+
+fn parse_address_chain(value: &str) -> anyhow::Result<AddressChain> {
+  match AddressChain::from_name(value) {
+    Some(chain) => Ok(chain),
+    None => bail!(
+      "Invalid --address-chain value '{}': expected receive, change or both",
+      value
+    ),
   }
 }
 
+/// Parses an account index (`3`) or an inclusive range of accounts (`0-2`).
+pub fn parse_account_range(value: &str) -> anyhow::Result<RangeInclusive<u32>> {
+  let parse = |v: &str| -> anyhow::Result<u32> {
+    match v.trim().parse::<u32>() {
+      Ok(n) if n <= wallet::MAX_ACCOUNT => Ok(n),
+      _ => bail!(
+        "Invalid account '{}': expected a number from 0 to {}",
+        v.trim(),
+        wallet::MAX_ACCOUNT
+      ),
+    }
+  };
+
+  let (start, end) = match value.split_once('-') {
+    Some((start, end)) => (parse(start)?, parse(end)?),
+    None => {
+      let n = parse(value)?;
+      (n, n)
+    }
+  };
+
+  if start > end {
+    bail!("Invalid account range '{}': start is after end", value);
+  }
+
+  Ok(start..=end)
+}
+
+/// Reads the passphrase from a file, dropping a single trailing line break
+/// so that files created with `echo` behave as expected.
+pub fn read_passphrase_file(path: &PathBuf) -> anyhow::Result<Zeroizing<String>> {
+  read_secret_file(path, "passphrase")
+}
+
+/// Reads the SLIP-39 passphrase from a file, like [`read_passphrase_file`].
+pub fn read_slip39_passphrase_file(path: &PathBuf) -> anyhow::Result<Zeroizing<String>> {
+  read_secret_file(path, "SLIP-39 passphrase")
+}
+
+/// Reads an export password from a file, like [`read_passphrase_file`].
+pub fn read_password_file(path: &PathBuf) -> anyhow::Result<Zeroizing<String>> {
+  read_secret_file(path, "password")
+}
+
+fn read_secret_file(path: &PathBuf, what: &str) -> anyhow::Result<Zeroizing<String>> {
+  let mut secret = Zeroizing::new(
+    std::fs::read_to_string(path)
+      .with_context(|| format!("Unable to read {} file {}", what, path.display()))?,
+  );
+
+  if secret.ends_with('\n') {
+    secret.pop();
+    if secret.ends_with('\r') {
+      secret.pop();
+    }
+  }
+
+  Ok(secret)
+}
+
 pub fn print_version() {
   println!(
     "{} {} ({} {})",
@@ -61,4 +524,139 @@ pub fn print_about() {
     style("- Documentation: ").bold().yellow(),
     meta::PROJECT_REPOSITORY
   );
+  print_usage();
+}
+
+pub fn print_usage() {
+  println!();
+  println!(
+    "{}",
+    style(format!("Usage: {}", "-".repeat(67))).cyan().bold()
+  );
+  println!("  {} [OPTIONS]", meta::PROJECT_NAME);
+  println!(
+    "  {} decrypt <FILE> [--output <PATH|->] [--password-file <PATH>]\n",
+    meta::PROJECT_NAME
+  );
+  println!("  Options not given are asked interactively.\n");
+  for (flag, help) in [
+    (
+      "--offline <require|warn|off>",
+      "Network check at startup (default warn)",
+    ),
+    ("--require-offline", "Same as --offline require"),
+    ("--words <12|15|18|21|24>", "Mnemonic size"),
+    (
+      "--dice <SEQUENCE>",
+      "Roll sequence, implies --dice-mode manual",
+    ),
+    ("--dice-mode <manual|auto>", "Dice entropy mode"),
+    (
+      "--dice-source <d6|coin|d4|d8|d10|d12|d20>",
+      "Dice or coin used for manual rolls (default d6)",
+    ),
+    (
+      "--dice-scheme <seedctl|coldcard|raw>",
+      "Dice-to-entropy conversion (default seedctl)",
+    ),
+    (
+      "--language <NAME>",
+      "BIP39 wordlist language (default english)",
+    ),
+    ("--network <bitcoin|testnet>", "Network"),
+    ("--script-type <bip84|bip49|bip44|bip86>", "Address type"),
+    (
+      "--account <N|N-M>",
+      "Account index or range of accounts (default 0)",
+    ),
+    ("--address-start <N>", "First address index (default 0)"),
+    (
+      "--address-count <N>",
+      "Number of addresses per chain (default 10, at most 10000)",
+    ),
+    (
+      "--address-chain <receive|change|both>",
+      "Address chain to derive (default receive)",
+    ),
+    (
+      "--mnemonic-file <PATH>",
+      "Import an existing BIP39 mnemonic from a file",
+    ),
+    (
+      "--slip39-shares <PATH>",
+      "Recover from SLIP-39 shares in a file, one per line",
+    ),
+    (
+      "--slip39-passphrase-file <PATH>",
+      "Read the passphrase of the SLIP-39 shares from a file",
+    ),
+    (
+      "--slip39 <SPEC>",
+      "Also split the seed into SLIP-39 shares (e.g. 2of3)",
+    ),
+    (
+      "--seedqr <standard|compact>",
+      "Show the mnemonic as a SeedQR / CompactSeedQR",
+    ),
+    (
+      "--seedqr-file <PATH>",
+      "Write the SeedQR to PATH (.png or SVG)",
+    ),
+    (
+      "--display <pages|words|plain>",
+      "How secrets are shown (default pages, plain with --yes)",
+    ),
+    (
+      "--passphrase-file <PATH>",
+      "Read the BIP39 passphrase from a file",
+    ),
+    (
+      "--export <PATH>",
+      "Write the watch-only wallet JSON to PATH",
+    ),
+    (
+      "--export-private <PATH>",
+      "Write the wallet JSON with the account private key to PATH",
+    ),
+    (
+      "--export-core <PATH>",
+      "Write a Bitcoin Core importdescriptors payload to PATH",
+    ),
+    (
+      "--export-electrum <PATH>",
+      "Write an Electrum watch-only wallet file to PATH",
+    ),
+    (
+      "--export-sparrow <PATH>",
+      "Write a Sparrow / Coldcard-style generic JSON to PATH",
+    ),
+    (
+      "--export-addresses <PATH>",
+      "Write the derived addresses to PATH (.csv or JSON)",
+    ),
+    (
+      "--encrypt-password-file <PATH>",
+      "Encrypt every export (age) with the password in PATH",
+    ),
+    (
+      "--backup-sheet <PATH>",
+      "Write a printable backup sheet to PATH (.pdf or SVG)",
+    ),
+    (
+      "--backup-sheet-mode <full|template|public>",
+      "Contents of the backup sheet (default full)",
+    ),
+    (
+      "-y, --yes",
+      "Skip confirmations and use defaults for missing options",
+    ),
+    ("-V, --version", "Print version"),
+    ("-h, --help, --about", "Print this information"),
+  ] {
+    println!(
+      "  {}{}",
+      style(format!("{:<44}", flag)).bold().yellow(),
+      help
+    );
+  }
 }
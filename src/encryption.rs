@@ -0,0 +1,128 @@
+//! Password-encrypted export files in the age format (scrypt recipient), so
+//! they can also be opened with the reference `age -d` tool.
+
+use age::{
+  DecryptError, Decryptor, Identity,
+  scrypt::{self, Recipient},
+  secrecy::SecretString,
+};
+use anyhow::{Context, bail};
+use std::{
+  io::{Read, Write},
+  iter,
+  path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+/// Extension appended to encrypted files.
+pub const EXTENSION: &str = "age";
+
+/// First line of every binary age file.
+const MAGIC: &[u8] = b"age-encryption.org/v1\n";
+
+/// Encrypts `plaintext` with `password`.
+pub fn encrypt(plaintext: &[u8], password: &str) -> anyhow::Result<Vec<u8>> {
+  if password.is_empty() {
+    bail!("The encryption password cannot be empty");
+  }
+
+  let recipient = Recipient::new(SecretString::from(password.to_owned()));
+  let mut ciphertext = Vec::with_capacity(plaintext.len() + 256);
+  let mut writer = age::Encryptor::with_recipients(iter::once(&recipient as _))?
+    .wrap_output(&mut ciphertext)
+    .context("Unable to encrypt the export")?;
+  writer.write_all(plaintext)?;
+  writer.finish()?;
+  Ok(ciphertext)
+}
+
+/// Decrypts a file written by [`encrypt`] (or by `age -p`).
+pub fn decrypt(ciphertext: &[u8], password: &str) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  if !is_encrypted(ciphertext) {
+    bail!("Not an age-encrypted file");
+  }
+
+  let decryptor = Decryptor::new_buffered(ciphertext)?;
+  if !decryptor.is_scrypt() {
+    bail!("The file is encrypted to a key, not with a password");
+  }
+
+  let identity = scrypt::Identity::new(SecretString::from(password.to_owned()));
+  let mut reader = match decryptor.decrypt(iter::once(&identity as &dyn Identity)) {
+    Ok(reader) => reader,
+    Err(DecryptError::DecryptionFailed | DecryptError::NoMatchingKeys) => {
+      bail!("Wrong password, or the file is damaged")
+    }
+    Err(e) => return Err(e.into()),
+  };
+
+  // The plaintext is never larger than the ciphertext, so the buffer is not
+  // reallocated (and copied) while reading
+  let mut plaintext = Zeroizing::new(Vec::with_capacity(ciphertext.len()));
+  reader.read_to_end(&mut plaintext)?;
+  Ok(plaintext)
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+  data.starts_with(MAGIC)
+}
+
+/// `path` with the `.age` extension appended, unless it already has it.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+  match path.extension() {
+    Some(ext) if ext == EXTENSION => path.to_path_buf(),
+    _ => {
+      let mut name = path.as_os_str().to_owned();
+      name.push(".");
+      name.push(EXTENSION);
+      PathBuf::from(name)
+    }
+  }
+}
+
+/// `path` without its `.age` extension, where the decrypted file goes.
+pub fn decrypted_path(path: &Path) -> Option<PathBuf> {
+  match path.extension() {
+    Some(ext) if ext == EXTENSION => Some(path.with_extension("")),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let ciphertext = encrypt(b"watch-only wallet", "correct horse").unwrap();
+    assert!(is_encrypted(&ciphertext));
+    assert_eq!(
+      decrypt(&ciphertext, "correct horse").unwrap().as_slice(),
+      b"watch-only wallet"
+    );
+    assert!(decrypt(&ciphertext, "wrong horse").is_err());
+  }
+
+  #[test]
+  fn rejects_empty_password_and_plaintext() {
+    assert!(encrypt(b"data", "").is_err());
+    assert!(decrypt(b"{\"plain\": true}", "password").is_err());
+  }
+
+  #[test]
+  fn paths() {
+    assert_eq!(
+      encrypted_path(Path::new("wallet.json")),
+      Path::new("wallet.json.age")
+    );
+    assert_eq!(
+      encrypted_path(Path::new("wallet.json.age")),
+      Path::new("wallet.json.age")
+    );
+    assert_eq!(
+      decrypted_path(Path::new("dir/sheet.pdf.age")).unwrap(),
+      Path::new("dir/sheet.pdf")
+    );
+    assert_eq!(decrypted_path(Path::new("sheet.pdf")), None);
+  }
+}
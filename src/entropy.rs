@@ -0,0 +1,567 @@
+use rand::RngExt;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+pub fn dice_hash(dice: &[u8]) -> Zeroizing<Vec<u8>> {
+  Zeroizing::new(Sha256::digest(dice).to_vec())
+}
+
+pub fn generate_system_entropy(bytes: usize) -> Zeroizing<Vec<u8>> {
+  let mut rng = rand::rng();
+  Zeroizing::new((0..bytes).map(|_| rng.random::<u8>()).collect())
+}
+
+pub fn combine_entropy(a: &[u8], b: &[u8]) -> Zeroizing<Vec<u8>> {
+  let mut hasher = Sha256::new();
+  hasher.update(a);
+  hasher.update(b);
+  Zeroizing::new(hasher.finalize().to_vec())
+}
+
+/// First `bits` bits of `entropy`. Every BIP39 size is a multiple of 32 bits,
+/// so whole bytes are kept.
+pub fn truncate_entropy(entropy: &[u8], bits: usize) -> Zeroizing<Vec<u8>> {
+  Zeroizing::new(entropy[..bits / 8].to_vec())
+}
+
+/// Physical source of the rolls. Values are stored as the face read: `1..=N`
+/// for most dice, `0..=9` for a d10 and `0`/`1` (tails/heads) for a coin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntropySource {
+  Coin,
+  D4,
+  D6,
+  D8,
+  D10,
+  D12,
+  D20,
+}
+
+impl EntropySource {
+  pub const ALL: [EntropySource; 7] = [
+    EntropySource::D6,
+    EntropySource::Coin,
+    EntropySource::D4,
+    EntropySource::D8,
+    EntropySource::D10,
+    EntropySource::D12,
+    EntropySource::D20,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      EntropySource::Coin => "coin",
+      EntropySource::D4 => "d4",
+      EntropySource::D6 => "d6",
+      EntropySource::D8 => "d8",
+      EntropySource::D10 => "d10",
+      EntropySource::D12 => "d12",
+      EntropySource::D20 => "d20",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|s| s.name() == name)
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      EntropySource::Coin => "Coin flips (0/1 or h/t)",
+      EntropySource::D4 => "d4 (1-4)",
+      EntropySource::D6 => "d6 (1-6)",
+      EntropySource::D8 => "d8 (1-8)",
+      EntropySource::D10 => "d10 (0-9)",
+      EntropySource::D12 => "d12 (1-12)",
+      EntropySource::D20 => "d20 (1-20)",
+    }
+  }
+
+  pub fn sides(self) -> u8 {
+    match self {
+      EntropySource::Coin => 2,
+      EntropySource::D4 => 4,
+      EntropySource::D6 => 6,
+      EntropySource::D8 => 8,
+      EntropySource::D10 => 10,
+      EntropySource::D12 => 12,
+      EntropySource::D20 => 20,
+    }
+  }
+
+  pub fn min_face(self) -> u8 {
+    match self {
+      EntropySource::Coin | EntropySource::D10 => 0,
+      _ => 1,
+    }
+  }
+
+  pub fn max_face(self) -> u8 {
+    self.min_face() + self.sides() - 1
+  }
+
+  pub fn bits_per_roll(self) -> f64 {
+    f64::from(self.sides()).log2()
+  }
+
+  pub fn required_rolls(self, bits: usize) -> usize {
+    ((bits as f64) / self.bits_per_roll()).ceil() as usize
+  }
+
+  /// Faces above 9 need two digits, so such rolls are written separated.
+  pub fn multi_digit(self) -> bool {
+    self.max_face() > 9
+  }
+
+  /// Value of a single-key symbol other than a digit (`h`/`t` for a coin).
+  pub fn letter_value(self, c: char) -> Option<u8> {
+    match (self, c.to_ascii_lowercase()) {
+      (EntropySource::Coin, 'h') => Some(1),
+      (EntropySource::Coin, 't') => Some(0),
+      _ => None,
+    }
+  }
+
+  pub fn is_face(self, value: u8) -> bool {
+    (self.min_face()..=self.max_face()).contains(&value)
+  }
+
+  /// Parses a roll sequence such as `31462...` or, for faces above 9,
+  /// `12 3 20` / `12,3,20`.
+  pub fn parse_rolls(self, input: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    let separated = input.contains([' ', ',']);
+    let tokens: Vec<&str> = if separated {
+      input.split([' ', ',']).filter(|t| !t.is_empty()).collect()
+    } else if self.multi_digit() {
+      return Err(format!(
+        "{} rolls must be separated by spaces or commas",
+        self.name()
+      ));
+    } else {
+      input
+        .char_indices()
+        .map(|(i, c)| &input[i..i + c.len_utf8()])
+        .collect()
+    };
+
+    let mut rolls = Zeroizing::new(Vec::with_capacity(tokens.len()));
+    for token in tokens {
+      rolls.push(self.parse_roll(token)?);
+    }
+    Ok(rolls)
+  }
+
+  /// Parses a single roll: a face value (`7`, `12`) or a letter (`h`).
+  pub fn parse_roll(self, token: &str) -> Result<u8, String> {
+    let value = match token.chars().next().and_then(|c| self.letter_value(c)) {
+      Some(value) if token.len() == 1 => Some(value),
+      _ => token.parse::<u8>().ok().filter(|v| self.is_face(*v)),
+    };
+    value.ok_or_else(|| format!("'{}' is not a valid roll for {}", token, self.label()))
+  }
+
+  /// Rolls as typed: contiguous digits, or space separated for faces
+  /// above 9.
+  pub fn format_rolls(self, rolls: &[u8]) -> Zeroizing<String> {
+    let separator = if self.multi_digit() { " " } else { "" };
+    Zeroizing::new(
+      rolls
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(separator),
+    )
+  }
+
+  pub fn generate_random_rolls(self, count: usize) -> Zeroizing<Vec<u8>> {
+    let mut rng = rand::rng();
+    Zeroizing::new(
+      (0..count)
+        .map(|_| rng.random_range(self.min_face()..=self.max_face()))
+        .collect(),
+    )
+  }
+
+  /// Chi-squared critical value at p = 0.01 for `sides - 1` degrees of
+  /// freedom. A fair source exceeds it only once in a hundred sequences.
+  pub fn chi_squared_critical(self) -> f64 {
+    match self {
+      EntropySource::Coin => 6.635,
+      EntropySource::D4 => 11.345,
+      EntropySource::D6 => 15.086,
+      EntropySource::D8 => 18.475,
+      EntropySource::D10 => 21.666,
+      EntropySource::D12 => 24.725,
+      EntropySource::D20 => 36.191,
+    }
+  }
+
+  /// Rolls needed before the chi-squared test means anything (five expected
+  /// per face).
+  pub fn min_rolls_for_chi_squared(self) -> usize {
+    usize::from(self.sides()) * 5
+  }
+}
+
+/// How rolls are turned into entropy. Every scheme uses all the rolls
+/// given; only the conversion differs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiceScheme {
+  /// SHA-256 over the raw roll values (one byte per roll). seedctl's
+  /// original scheme, kept as the default so earlier backups can be
+  /// regenerated.
+  Seedctl,
+  /// SHA-256 over the ASCII roll string ("3614..."), as done by Coldcard,
+  /// SeedSigner and the iancoleman BIP39 tool with a fixed mnemonic length.
+  /// Those tools only take d6 rolls.
+  Coldcard,
+  /// Rolls read as one base-N number (the highest face counts as 0) whose
+  /// trailing bits are used directly, as the iancoleman BIP39 tool does with
+  /// "raw entropy".
+  Raw,
+}
+
+impl DiceScheme {
+  pub const ALL: [DiceScheme; 3] = [DiceScheme::Seedctl, DiceScheme::Coldcard, DiceScheme::Raw];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      DiceScheme::Seedctl => "seedctl",
+      DiceScheme::Coldcard => "coldcard",
+      DiceScheme::Raw => "raw",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|s| s.name() == name)
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      DiceScheme::Seedctl => "seedctl (SHA-256 of roll values)",
+      DiceScheme::Coldcard => "Coldcard / SeedSigner (SHA-256 of roll string)",
+      DiceScheme::Raw => "BIP39 tool raw entropy (base-N conversion)",
+    }
+  }
+
+  pub fn supports(self, source: EntropySource) -> bool {
+    self != DiceScheme::Coldcard || source == EntropySource::D6
+  }
+
+  /// Entropy for `bits` bits of mnemonic. The result holds at least
+  /// `bits / 8` bytes when at least [`EntropySource::required_rolls`] rolls
+  /// are given.
+  pub fn entropy(self, source: EntropySource, rolls: &[u8], bits: usize) -> Zeroizing<Vec<u8>> {
+    match self {
+      DiceScheme::Seedctl => dice_hash(rolls),
+      DiceScheme::Coldcard => {
+        let rolls = source.format_rolls(rolls);
+        Zeroizing::new(Sha256::digest(rolls.as_bytes()).to_vec())
+      }
+      DiceScheme::Raw => raw_entropy(source, rolls, bits),
+    }
+  }
+}
+
+/// How a dice-generated mnemonic was built, shown in the output and recorded
+/// in the export so the same rolls can be checked on another tool.
+#[derive(Clone, Copy, Debug)]
+pub struct DiceOrigin {
+  pub source: EntropySource,
+  pub scheme: DiceScheme,
+  pub hybrid: bool,
+  pub rolls: usize,
+}
+
+/// Trailing `bits` bits of the rolls read as a base-N number. The iancoleman
+/// tool left-pads the number to `floor(rolls * log2(N))` bits and then drops
+/// the leading ones (`start = bits.length - bitsToUse`), which leaves the
+/// value modulo 2^bits.
+fn raw_entropy(source: EntropySource, rolls: &[u8], bits: usize) -> Zeroizing<Vec<u8>> {
+  let base = source.sides();
+  let len = bits.div_ceil(8);
+  // Big-endian accumulator: N < 32, so five bits per roll are enough
+  let size = (rolls.len() * 5 / 8 + 1).max(len);
+  let mut value = Zeroizing::new(vec![0u8; size]);
+
+  for &roll in rolls {
+    mul_add(&mut value, u32::from(base), u32::from(roll % base));
+  }
+
+  let mut out = Zeroizing::new(value[size - len..].to_vec());
+  if !bits.is_multiple_of(8) {
+    out[0] &= 0xff >> (8 - bits % 8);
+  }
+  out
+}
+
+fn mul_add(num: &mut [u8], mul: u32, add: u32) {
+  let mut carry = add;
+  for byte in num.iter_mut().rev() {
+    let v = u32::from(*byte) * mul + carry;
+    *byte = v as u8;
+    carry = v >> 8;
+  }
+}
+
+/// Quality indicators for a roll sequence. They cannot prove that dice are
+/// fair, only flag sequences that are very unlikely to be.
+pub struct DiceStats {
+  /// Count per face, starting at [`EntropySource::min_face`].
+  pub counts: Vec<usize>,
+  pub chi_squared: Option<f64>,
+  pub longest_run: usize,
+  pub warnings: Vec<String>,
+}
+
+impl DiceStats {
+  pub fn from_rolls(source: EntropySource, rolls: &[u8]) -> Self {
+    let sides = usize::from(source.sides());
+    let mut counts = vec![0usize; sides];
+    for &roll in rolls {
+      counts[usize::from(roll - source.min_face())] += 1;
+    }
+
+    let n = rolls.len();
+    let chi_squared = (n >= source.min_rolls_for_chi_squared()).then(|| {
+      let expected = n as f64 / sides as f64;
+      counts
+        .iter()
+        .map(|&c| (c as f64 - expected).powi(2) / expected)
+        .sum()
+    });
+
+    let longest_run = if n == 0 {
+      0
+    } else {
+      longest_match(rolls, 1) + 1
+    };
+    let mut warnings = Vec::new();
+
+    if let Some(chi) = chi_squared
+      && chi > source.chi_squared_critical()
+    {
+      warnings.push(format!(
+        "Face counts are uneven (chi-squared {:.2} > {:.2})",
+        chi,
+        source.chi_squared_critical()
+      ));
+    }
+
+    // A long run also repeats with period 2 and 3; report it only once
+    let long_run = n > 0 && unlikely(sides, n, longest_run - 1);
+    if long_run {
+      warnings.push(format!(
+        "The same face came up {} times in a row",
+        longest_run
+      ));
+    }
+
+    for period in [2, 3].into_iter().filter(|_| !long_run) {
+      let repeats = longest_match(rolls, period);
+      if repeats >= PATTERN_MATCHES && unlikely(sides, n, repeats) {
+        warnings.push(format!(
+          "A {}-roll pattern repeats over {} rolls",
+          period,
+          repeats + period
+        ));
+      }
+    }
+
+    if sides >= STRAIGHT_LEN && contains_straight(rolls) {
+      warnings.push(format!(
+        "Contains {} consecutive faces in order (e.g. 123456), typical of typed input",
+        STRAIGHT_LEN
+      ));
+    }
+
+    DiceStats {
+      counts,
+      chi_squared,
+      longest_run,
+      warnings,
+    }
+  }
+
+  pub fn is_suspicious(&self) -> bool {
+    !self.warnings.is_empty()
+  }
+}
+
+/// Consecutive matches that make a short repeating pattern worth reporting.
+const PATTERN_MATCHES: usize = 6;
+
+/// Length of an ascending or descending sequence of faces worth reporting.
+const STRAIGHT_LEN: usize = 6;
+
+/// Longest stretch where each roll equals the one `period` rolls earlier.
+/// With `period == 1` this is the longest run minus one.
+fn longest_match(rolls: &[u8], period: usize) -> usize {
+  let (mut best, mut current) = (0, 0);
+  for i in period..rolls.len() {
+    if rolls[i] == rolls[i - period] {
+      current += 1;
+      best = best.max(current);
+    } else {
+      current = 0;
+    }
+  }
+  best
+}
+
+/// True when `matches` consecutive rolls agreeing with an earlier roll are
+/// expected in fewer than 1% of fair sequences of length `n`.
+fn unlikely(sides: usize, n: usize, matches: usize) -> bool {
+  n as f64 * (1.0 / sides as f64).powi(matches as i32) < 0.01
+}
+
+fn contains_straight(rolls: &[u8]) -> bool {
+  rolls.windows(STRAIGHT_LEN).any(|w| {
+    w.windows(2).all(|p| p[1] == p[0].wrapping_add(1))
+      || w.windows(2).all(|p| p[0] == p[1].wrapping_add(1))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// 50 d6 rolls, enough for 128 bits.
+  const D6_ROLLS: &str = "31415626535314156265353141562653531415626535314156";
+
+  fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn seedctl_scheme_hashes_roll_values() {
+    let rolls = EntropySource::D6.parse_rolls(D6_ROLLS).unwrap();
+    assert_eq!(
+      hex(&DiceScheme::Seedctl.entropy(EntropySource::D6, &rolls, 128)),
+      "0ec1db69d6362ea4a391c77851b71040ff72189ef80e19386e1aff31d6f70650"
+    );
+  }
+
+  #[test]
+  fn coldcard_scheme_hashes_roll_string() {
+    let rolls = EntropySource::D6.parse_rolls(D6_ROLLS).unwrap();
+    assert_eq!(
+      hex(&DiceScheme::Coldcard.entropy(EntropySource::D6, &rolls, 128)),
+      "560a2e125dc93e8a96ef3e056ebecc722409190349277e51d2b9560e1bac3e03"
+    );
+    // SHA-256("123456")
+    assert_eq!(
+      hex(&DiceScheme::Coldcard.entropy(EntropySource::D6, &[1, 2, 3, 4, 5, 6], 128)),
+      "8d969eef6ecad3c29a3a629280e686cf0c3f5d5a86aff3ca12020c923adc6c92"
+    );
+    assert!(!DiceScheme::Coldcard.supports(EntropySource::D20));
+  }
+
+  #[test]
+  fn raw_scheme_reads_base_n() {
+    // 50 d6 rolls are 129 bits; the iancoleman tool keeps the last 128
+    let rolls = EntropySource::D6.parse_rolls(D6_ROLLS).unwrap();
+    assert_eq!(
+      hex(&DiceScheme::Raw.entropy(EntropySource::D6, &rolls, 128)),
+      "4d0efaa5f1f3798d00a6221af5ef6092"
+    );
+
+    let rolls: Vec<u8> = (0..30).map(|i| (i * 7) % 20 + 1).collect();
+    assert_eq!(
+      hex(&DiceScheme::Raw.entropy(EntropySource::D20, &rolls, 128)),
+      "3a129ed60f60dd1acfb968c76724d2b8"
+    );
+
+    // The highest face counts as 0
+    assert_eq!(
+      hex(&DiceScheme::Raw.entropy(EntropySource::D6, &[6; 50], 128)),
+      "0".repeat(32)
+    );
+  }
+
+  #[test]
+  fn parses_single_rolls() {
+    assert_eq!(EntropySource::D12.parse_roll("12"), Ok(12));
+    assert_eq!(EntropySource::D20.parse_roll("7"), Ok(7));
+    assert_eq!(EntropySource::D20.parse_roll("20"), Ok(20));
+    assert_eq!(EntropySource::D10.parse_roll("0"), Ok(0));
+    assert_eq!(EntropySource::Coin.parse_roll("h"), Ok(1));
+    assert_eq!(EntropySource::Coin.parse_roll("T"), Ok(0));
+    assert!(EntropySource::D6.parse_roll("7").is_err());
+    assert!(EntropySource::D12.parse_roll("0").is_err());
+    assert!(EntropySource::D20.parse_roll("21").is_err());
+    assert!(EntropySource::Coin.parse_roll("ht").is_err());
+  }
+
+  #[test]
+  fn parses_roll_sequences() {
+    assert_eq!(
+      EntropySource::D6.parse_rolls("3146").unwrap().as_slice(),
+      &[3, 1, 4, 6]
+    );
+    assert_eq!(
+      EntropySource::D20
+        .parse_rolls("12 3,20")
+        .unwrap()
+        .as_slice(),
+      &[12, 3, 20]
+    );
+    assert_eq!(
+      EntropySource::Coin.parse_rolls("hTt1").unwrap().as_slice(),
+      &[1, 0, 0, 1]
+    );
+    // Faces above 9 are ambiguous without separators
+    assert!(EntropySource::D12.parse_rolls("1211").is_err());
+    assert!(EntropySource::D6.parse_rolls("3170").is_err());
+  }
+
+  #[test]
+  fn required_rolls_cover_the_bits() {
+    assert_eq!(EntropySource::D6.required_rolls(128), 50);
+    assert_eq!(EntropySource::D6.required_rolls(256), 100);
+    assert_eq!(EntropySource::Coin.required_rolls(128), 128);
+    assert_eq!(EntropySource::D20.required_rolls(128), 30);
+  }
+
+  #[test]
+  fn stats_flag_constant_rolls() {
+    let stats = DiceStats::from_rolls(EntropySource::D6, &[1; 50]);
+    assert_eq!(stats.counts, [50, 0, 0, 0, 0, 0]);
+    assert_eq!(stats.longest_run, 50);
+    assert!(stats.chi_squared.unwrap() > EntropySource::D6.chi_squared_critical());
+    assert!(
+      stats
+        .warnings
+        .iter()
+        .any(|w| w.contains("50 times in a row"))
+    );
+    assert!(stats.is_suspicious());
+  }
+
+  #[test]
+  fn stats_flag_sequential_rolls() {
+    let rolls: Vec<u8> = (0..54).map(|i| i % 6 + 1).collect();
+    let stats = DiceStats::from_rolls(EntropySource::D6, &rolls);
+    // Perfectly even counts, so only the pattern gives it away
+    assert_eq!(stats.chi_squared, Some(0.0));
+    assert_eq!(stats.warnings.len(), 1);
+    assert!(stats.warnings[0].contains("consecutive faces in order"));
+  }
+
+  #[test]
+  fn stats_accept_balanced_rolls() {
+    let rolls = EntropySource::D6.parse_rolls(D6_ROLLS).unwrap();
+    let stats = DiceStats::from_rolls(EntropySource::D6, &rolls);
+    assert_eq!(stats.counts, [10, 4, 9, 5, 13, 9]);
+    // Expected 50 / 6 per face: 55.33 / 8.33 = 6.64, under the 15.09 limit
+    let chi = stats.chi_squared.unwrap();
+    assert!((chi - 6.64).abs() < 1e-9);
+    assert!(chi < EntropySource::D6.chi_squared_critical());
+    assert!(!stats.is_suspicious(), "{:?}", stats.warnings);
+
+    // Too few rolls for the test to mean anything
+    assert_eq!(
+      DiceStats::from_rolls(EntropySource::D6, &rolls[..20]).chi_squared,
+      None
+    );
+  }
+}
@@ -1,4 +1,6 @@
 use serde::Serialize;
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
 
 #[derive(Serialize)]
 pub struct WalletExport {
@@ -6,11 +8,26 @@ pub struct WalletExport {
   pub network: String,
   pub script_type: String,
   pub key_origin: KeyOrigin,
+  /// Absent when the wallet was recovered from SLIP-39 shares.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub mnemonic: Option<MnemonicInfo>,
   pub watch_only: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dice: Option<DiceInfo>,
   pub keys: Keys,
   pub descriptors: Descriptors,
 }
 
+/// How the mnemonic was built from dice, for cross-checking the rolls on a
+/// hardware wallet or another tool. Absent for imported mnemonics.
+#[derive(Serialize)]
+pub struct DiceInfo {
+  pub source: String,
+  pub scheme: String,
+  pub mode: String,
+  pub rolls: usize,
+}
+
 #[derive(Serialize)]
 pub struct SoftwareInfo {
   pub name: String,
@@ -18,6 +35,15 @@ pub struct SoftwareInfo {
   pub repository: String,
 }
 
+/// Shape of the mnemonic behind the keys. The words themselves are never
+/// exported.
+#[derive(Serialize)]
+pub struct MnemonicInfo {
+  pub language: String,
+  pub word_count: usize,
+  pub entropy_bits: usize,
+}
+
 #[derive(Serialize)]
 pub struct KeyOrigin {
   pub fingerprint: String,
@@ -27,7 +53,7 @@ pub struct KeyOrigin {
 #[derive(Serialize)]
 pub struct Keys {
   pub account_xpub: String,
-  pub account_xprv: Option<String>,
+  pub account_xprv: Option<Zeroizing<String>>,
 }
 
 #[derive(Serialize)]
@@ -35,3 +61,143 @@ pub struct Descriptors {
   pub receive: String,
   pub change: String,
 }
+
+/// One entry of a Bitcoin Core `importdescriptors` RPC request.
+#[derive(Serialize)]
+pub struct CoreImportDescriptor {
+  pub desc: String,
+  pub active: bool,
+  pub internal: bool,
+  pub range: [u32; 2],
+  pub timestamp: String,
+}
+
+/// Electrum wallet file holding a single watch-only BIP32 keystore.
+/// Electrum infers the script type from the SLIP-132 prefix of `xpub`.
+#[derive(Serialize)]
+pub struct ElectrumWallet {
+  pub keystore: ElectrumKeystore,
+  pub wallet_type: String,
+  pub use_encryption: bool,
+  pub seed_version: u32,
+}
+
+#[derive(Serialize)]
+pub struct ElectrumKeystore {
+  #[serde(rename = "type")]
+  pub kind: String,
+  pub xpub: String,
+  pub derivation: String,
+  pub root_fingerprint: String,
+  pub label: String,
+}
+
+/// Wallet file version understood by every Electrum release since 3.3;
+/// newer releases upgrade it on load.
+pub const ELECTRUM_SEED_VERSION: u32 = 17;
+
+/// Coldcard "generic JSON" export, also read by Sparrow. Each account is
+/// keyed by its script type (`bip84`, `bip49`, ...).
+#[derive(Serialize)]
+pub struct GenericWalletExport {
+  pub chain: String,
+  pub xfp: String,
+  pub account: u32,
+  #[serde(flatten)]
+  pub accounts: BTreeMap<String, GenericAccount>,
+}
+
+#[derive(Serialize)]
+pub struct GenericAccount {
+  pub name: String,
+  pub deriv: String,
+  pub xpub: String,
+  #[serde(rename = "_pub", skip_serializing_if = "Option::is_none")]
+  pub slip132_pub: Option<String>,
+  pub desc: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub first: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AddressEntry {
+  pub account: u32,
+  pub chain: String,
+  pub index: u32,
+  pub path: String,
+  pub address: String,
+}
+
+pub fn addresses_to_csv(entries: &[AddressEntry]) -> String {
+  let mut csv = String::from("account,chain,index,path,address\n");
+  for e in entries {
+    csv.push_str(&format!(
+      "{},{},{},{},{}\n",
+      e.account, e.chain, e.index, e.path, e.address
+    ));
+  }
+  csv
+}
+
+/// Range Bitcoin Core pre-derives for each imported descriptor.
+pub const CORE_IMPORT_RANGE: [u32; 2] = [0, 999];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+  /// seedctl's own `WalletExport` JSON, public keys only
+  Seedctl,
+  /// seedctl's `WalletExport` JSON including the account private key
+  SeedctlPrivate,
+  /// Payload for `bitcoin-cli importdescriptors`
+  BitcoinCore,
+  /// Electrum watch-only wallet file
+  Electrum,
+  /// Sparrow / Coldcard-style generic JSON
+  Sparrow,
+  /// Derived addresses with their paths
+  AddressesCsv,
+  AddressesJson,
+}
+
+impl ExportFormat {
+  /// Formats offered in the export menu. `SeedctlPrivate` is never part of
+  /// it: it has its own confirmation.
+  pub const WATCH_ONLY: [ExportFormat; 6] = [
+    ExportFormat::Seedctl,
+    ExportFormat::BitcoinCore,
+    ExportFormat::Electrum,
+    ExportFormat::Sparrow,
+    ExportFormat::AddressesCsv,
+    ExportFormat::AddressesJson,
+  ];
+
+  pub fn label(self) -> &'static str {
+    match self {
+      ExportFormat::Seedctl => "seedctl JSON (xpub + fingerprint + path)",
+      ExportFormat::SeedctlPrivate => "seedctl JSON with the account private key",
+      ExportFormat::BitcoinCore => "Bitcoin Core (importdescriptors)",
+      ExportFormat::Electrum => "Electrum (wallet file)",
+      ExportFormat::Sparrow => "Sparrow / generic JSON (Coldcard-style)",
+      ExportFormat::AddressesCsv => "Address list (CSV)",
+      ExportFormat::AddressesJson => "Address list (JSON)",
+    }
+  }
+
+  /// File name used when exporting interactively. Account 0 keeps the
+  /// historical names; other accounts get an `-account<N>` suffix.
+  pub fn default_file_name(self, fingerprint: &str, account: u32) -> String {
+    let id = match account {
+      0 => fingerprint.to_string(),
+      n => format!("{}-account{}", fingerprint, n),
+    };
+    match self {
+      ExportFormat::Seedctl => format!("wallet-{}-watch-only.json", id),
+      ExportFormat::SeedctlPrivate => format!("wallet-{}-private.json", id),
+      ExportFormat::BitcoinCore => format!("wallet-{}-core-descriptors.json", id),
+      ExportFormat::Electrum => format!("wallet-{}-electrum.json", id),
+      ExportFormat::Sparrow => format!("wallet-{}-sparrow.json", id),
+      ExportFormat::AddressesCsv => format!("wallet-{}-addresses.csv", id),
+      ExportFormat::AddressesJson => format!("wallet-{}-addresses.json", id),
+    }
+  }
+}
@@ -0,0 +1,324 @@
+//! Process protections applied at startup, and checks on the environment
+//! before secrets are displayed. Each risk names what could leak them.
+
+use std::{env, process::Command};
+
+/// One way the secrets on screen or in memory could leak.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Risk {
+  /// Short name of the check (`swap`, `ssh`, ...).
+  pub source: String,
+  pub reason: String,
+}
+
+/// How much of the process memory is locked into RAM, out of reach of swap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryLock {
+  /// Everything, including memory allocated later.
+  All,
+  /// Only the pages mapped when the lock was taken.
+  Current,
+  None,
+}
+
+/// Programs that record a terminal session, by process name.
+#[cfg(target_os = "linux")]
+const RECORDERS: [&str; 4] = ["script", "asciinema", "ttyrec", "termrec"];
+
+/// Keeps secrets out of core dumps: marks the process non-dumpable (which
+/// also stops unprivileged debuggers from attaching) and sets the core size
+/// limit to zero. Failures show up later in [`inspect`].
+///
+/// All memory, current and future, is also locked when that cannot make
+/// later allocations fail, that is when the locked memory limit does not
+/// apply: scrypt alone allocates far more than the usual `ulimit -l`.
+pub fn protect_process() -> MemoryLock {
+  #[cfg(target_os = "linux")]
+  // SAFETY: PR_SET_DUMPABLE takes an integer argument and touches no memory
+  unsafe {
+    libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0);
+  }
+  #[cfg(unix)]
+  {
+    let limit = libc::rlimit {
+      rlim_cur: 0,
+      rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid rlimit for the duration of the call
+    unsafe {
+      libc::setrlimit(libc::RLIMIT_CORE, &limit);
+    }
+  }
+
+  #[cfg(target_os = "linux")]
+  // SAFETY: mlockall takes flags only
+  if linux::unlimited_memlock()
+    && unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) } == 0
+  {
+    return MemoryLock::All;
+  }
+  MemoryLock::None
+}
+
+/// Locks the pages the process has mapped into RAM right now, so the secrets
+/// derived so far cannot be swapped out, unless [`protect_process`] already
+/// locked everything. Fails when the process is larger than `ulimit -l`.
+pub fn lock_memory(lock: MemoryLock) -> MemoryLock {
+  if lock == MemoryLock::All {
+    return lock;
+  }
+  #[cfg(target_os = "linux")]
+  // SAFETY: mlockall takes flags only
+  if unsafe { libc::mlockall(libc::MCL_CURRENT) } == 0 {
+    return MemoryLock::Current;
+  }
+  MemoryLock::None
+}
+
+/// Inspects the environment. Swap is a risk for any memory `lock` (from
+/// [`lock_memory`]) leaves out.
+pub fn inspect(lock: MemoryLock) -> Vec<Risk> {
+  let mut risks = Vec::new();
+
+  #[cfg(target_os = "linux")]
+  {
+    let swaps = linux::swaps();
+    let unlocked = match lock {
+      MemoryLock::All => None,
+      MemoryLock::Current => Some(
+        "only memory in use before the wallet was displayed is locked; later buffers \
+         (viewer, exports, shares, sheet) may be written to disk",
+      ),
+      MemoryLock::None => Some("memory could not be locked; secrets may be written to disk"),
+    };
+    if let Some(unlocked) = unlocked
+      && !swaps.is_empty()
+    {
+      risks.push(Risk {
+        source: "swap".to_string(),
+        reason: format!("enabled ({}) and {}", swaps.join(", "), unlocked),
+      });
+    }
+  }
+  #[cfg(not(target_os = "linux"))]
+  let _ = lock;
+
+  if let Some(reason) = core_dumps() {
+    risks.push(Risk {
+      source: "core dumps".to_string(),
+      reason,
+    });
+  }
+
+  #[cfg(target_os = "linux")]
+  if let Some((pid, name)) = linux::tracer() {
+    risks.push(Risk {
+      source: "debugger".to_string(),
+      reason: format!("process traced by `{}` (pid {})", name, pid),
+    });
+  }
+
+  if let Some(reason) = recording() {
+    risks.push(Risk {
+      source: "recording".to_string(),
+      reason,
+    });
+  }
+
+  if env::var_os("TMUX").is_some() && tmux_pane_piped() {
+    risks.push(Risk {
+      source: "tmux".to_string(),
+      reason: "pane output is piped to a command (pipe-pane logging)".to_string(),
+    });
+  }
+
+  if let Some(reason) = ssh_session() {
+    risks.push(Risk {
+      source: "ssh".to_string(),
+      reason,
+    });
+  }
+
+  risks
+}
+
+/// Why a crash could still write a core file, if it could.
+fn core_dumps() -> Option<String> {
+  #[cfg(target_os = "linux")]
+  // SAFETY: PR_GET_DUMPABLE takes no pointer arguments
+  if unsafe { libc::prctl(libc::PR_GET_DUMPABLE, 0, 0, 0, 0) } == 0 {
+    return None;
+  }
+
+  #[cfg(unix)]
+  {
+    let mut limit = libc::rlimit {
+      rlim_cur: 0,
+      rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid, writable rlimit for the duration of the call
+    if unsafe { libc::getrlimit(libc::RLIMIT_CORE, &mut limit) } != 0 {
+      return Some("core size limit could not be read".to_string());
+    }
+    match limit.rlim_cur {
+      0 => None,
+      libc::RLIM_INFINITY => Some("allowed (RLIMIT_CORE = unlimited)".to_string()),
+      size => Some(format!("allowed (RLIMIT_CORE = {} bytes)", size)),
+    }
+  }
+  #[cfg(not(unix))]
+  {
+    None
+  }
+}
+
+/// Terminal recorders among the parent processes, or announced through the
+/// environment.
+fn recording() -> Option<String> {
+  #[cfg(target_os = "linux")]
+  if let Some((pid, name)) = linux::ancestors()
+    .into_iter()
+    .find(|(_, name, cmdline)| RECORDERS.contains(&name.as_str()) || is_asciinema(cmdline))
+    .map(|(pid, name, _)| (pid, name))
+  {
+    return Some(format!(
+      "terminal session recorded by `{}` (pid {})",
+      name, pid
+    ));
+  }
+
+  // Set by `asciinema rec` for the recorded shell
+  if env::var_os("ASCIINEMA_REC").is_some() {
+    return Some("terminal session recorded by asciinema (ASCIINEMA_REC is set)".to_string());
+  }
+  None
+}
+
+/// asciinema 2 runs under the Python interpreter, so it only shows up in the
+/// command line.
+#[cfg(target_os = "linux")]
+fn is_asciinema(cmdline: &[String]) -> bool {
+  cmdline.iter().take(2).any(|arg| {
+    std::path::Path::new(arg)
+      .file_name()
+      .is_some_and(|name| name == "asciinema")
+  })
+}
+
+/// Whether tmux reports a `pipe-pane` command on the current pane.
+fn tmux_pane_piped() -> bool {
+  let mut command = Command::new("tmux");
+  command.args(["display-message", "-p"]);
+  if let Some(pane) = env::var_os("TMUX_PANE") {
+    command.arg("-t").arg(pane);
+  }
+  command
+    .arg("#{pane_pipe}")
+    .output()
+    .is_ok_and(|out| out.status.success() && out.stdout.trim_ascii() == b"1")
+}
+
+fn ssh_session() -> Option<String> {
+  // SSH_CONNECTION is "<client ip> <client port> <server ip> <server port>"
+  if let Some(client) = env::var("SSH_CONNECTION")
+    .ok()
+    .and_then(|conn| conn.split_whitespace().next().map(str::to_string))
+  {
+    return Some(format!("remote session from {}", client));
+  }
+  env::var("SSH_TTY")
+    .ok()
+    .map(|tty| format!("remote session on {}", tty))
+}
+
+/// Linux: swap, tracer and parent processes from `/proc`.
+#[cfg(target_os = "linux")]
+mod linux {
+  use std::fs;
+
+  /// Swap devices and files from `/proc/swaps`. zram devices are compressed
+  /// RAM, not disk, and are left out.
+  pub fn swaps() -> Vec<String> {
+    // Filename Type Size Used Priority
+    fs::read_to_string("/proc/swaps")
+      .unwrap_or_default()
+      .lines()
+      .skip(1)
+      .filter_map(|line| line.split_whitespace().next())
+      .filter(|name| !name.starts_with("/dev/zram"))
+      .map(str::to_string)
+      .collect()
+  }
+
+  /// Whether locking memory is exempt from `RLIMIT_MEMLOCK`: the limit is
+  /// infinite, or the process holds `CAP_IPC_LOCK`.
+  pub fn unlimited_memlock() -> bool {
+    const CAP_IPC_LOCK: u32 = 14;
+
+    let mut limit = libc::rlimit {
+      rlim_cur: 0,
+      rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid, writable rlimit for the duration of the call
+    let infinite = unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut limit) } == 0
+      && limit.rlim_cur == libc::RLIM_INFINITY;
+
+    let capable = fs::read_to_string("/proc/self/status")
+      .ok()
+      .and_then(|status| {
+        let caps = status
+          .lines()
+          .find_map(|line| line.strip_prefix("CapEff:"))?
+          .trim()
+          .to_string();
+        u64::from_str_radix(&caps, 16).ok()
+      })
+      .is_some_and(|caps| caps & (1 << CAP_IPC_LOCK) != 0);
+
+    infinite || capable
+  }
+
+  /// PID and name of the process tracing this one.
+  pub fn tracer() -> Option<(u32, String)> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let pid = status
+      .lines()
+      .find_map(|line| line.strip_prefix("TracerPid:"))?
+      .trim()
+      .parse()
+      .ok()
+      .filter(|pid| *pid != 0)?;
+    Some((pid, comm(pid).unwrap_or_else(|| "?".to_string())))
+  }
+
+  /// PID, name and command line of each parent process, up to init.
+  pub fn ancestors() -> Vec<(u32, String, Vec<String>)> {
+    let mut ancestors = Vec::new();
+    let mut pid = parent("self");
+    while let Some(current) = pid.filter(|pid| *pid > 1) {
+      let name = comm(current).unwrap_or_default();
+      let cmdline = fs::read(format!("/proc/{}/cmdline", current))
+        .unwrap_or_default()
+        .split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+      ancestors.push((current, name, cmdline));
+      pid = parent(&current.to_string());
+    }
+    ancestors
+  }
+
+  fn comm(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{}/comm", pid))
+      .ok()
+      .map(|s| s.trim().to_string())
+  }
+
+  fn parent(pid: &str) -> Option<u32> {
+    // pid (comm) state ppid ...; the name may itself contain spaces and ')'
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(1)?.parse().ok()
+  }
+}
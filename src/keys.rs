@@ -0,0 +1,225 @@
+use bitcoin::{
+  base58,
+  bip32::{ChainCode, Xpriv, Xpub},
+  hashes::{Hash, sha256d},
+};
+
+use std::ops::Deref;
+use zeroize::Zeroizing;
+
+use crate::wallet::ScriptType;
+
+pub fn format_key_origin(
+  fingerprint: [u8; 4],
+  purpose: u32,
+  coin_type: u32,
+  account: u32,
+) -> String {
+  format!(
+    "[{:02x}{:02x}{:02x}{:02x}/{}h/{}h/{}h]",
+    fingerprint[0], fingerprint[1], fingerprint[2], fingerprint[3], purpose, coin_type, account
+  )
+}
+
+/// Builds a ranged output descriptor with its BIP380 checksum appended.
+/// `xpub` must be a plain xpub/tpub: Bitcoin Core does not accept SLIP-132
+/// (ypub/zpub) keys inside descriptors.
+pub fn output_descriptor(purpose: u32, key_origin: &str, xpub: &str, chain: u32) -> String {
+  let descriptor = match purpose {
+    // BIP84
+    84 => format!("wpkh({}{}/{chain}/*)", key_origin, xpub),
+    // BIP49
+    49 => format!("sh(wpkh({}{}/{chain}/*))", key_origin, xpub),
+    // BIP44
+    44 => format!("pkh({}{}/{chain}/*)", key_origin, xpub),
+    // BIP86
+    86 => format!("tr({}{}/{chain}/*)", key_origin, xpub),
+    _ => unreachable!(),
+  };
+
+  let checksum = descriptor_checksum(&descriptor).expect("descriptor uses only valid characters");
+  format!("{}#{}", descriptor, checksum)
+}
+
+// DESCRIPTOR CHECKSUM (BIP380)
+
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn polymod(c: u64, val: u64) -> u64 {
+  let c0 = c >> 35;
+  let mut c = ((c & 0x7ffffffff) << 5) ^ val;
+  if c0 & 1 != 0 {
+    c ^= 0xf5dee51989;
+  }
+  if c0 & 2 != 0 {
+    c ^= 0xa9fdca3312;
+  }
+  if c0 & 4 != 0 {
+    c ^= 0x1bab10e32d;
+  }
+  if c0 & 8 != 0 {
+    c ^= 0x3706b1677a;
+  }
+  if c0 & 16 != 0 {
+    c ^= 0x644d626ffd;
+  }
+  c
+}
+
+/// Computes the 8-character BIP380 checksum of a descriptor (without the
+/// `#`). Returns `None` if the descriptor contains a character outside the
+/// descriptor charset.
+pub fn descriptor_checksum(descriptor: &str) -> Option<String> {
+  let mut c = 1u64;
+  let mut cls = 0u64;
+  let mut cls_count = 0;
+
+  for ch in descriptor.chars() {
+    let pos = INPUT_CHARSET.find(ch)? as u64;
+    c = polymod(c, pos & 31);
+    cls = cls * 3 + (pos >> 5);
+    cls_count += 1;
+    if cls_count == 3 {
+      c = polymod(c, cls);
+      cls = 0;
+      cls_count = 0;
+    }
+  }
+  if cls_count > 0 {
+    c = polymod(c, cls);
+  }
+  for _ in 0..8 {
+    c = polymod(c, 0);
+  }
+  c ^= 1;
+
+  Some(
+    (0..8)
+      .map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+      .collect(),
+  )
+}
+
+// KEY FORMATTERS
+
+pub fn xprv_to_zprv(xprv: &Xpriv) -> Zeroizing<String> {
+  convert_xprv_prefix(xprv, 0x04b2430c) // zprv
+}
+
+pub fn xpub_to_zpub(xpub: &Xpub) -> String {
+  let mut data = xpub.encode();
+  data[0..4].copy_from_slice(&[0x04, 0xB2, 0x47, 0x46]);
+  base58::encode_check(&data)
+}
+
+pub fn xprv_to_yprv(xprv: &Xpriv) -> Zeroizing<String> {
+  convert_xprv_prefix(xprv, 0x049d7878) // yprv
+}
+
+/// Converte xpub → ypub / zpub (SLIP-132)
+pub fn convert_xpub_prefix(xpub: &Xpub, version: u32) -> String {
+  // Decode Base58Check
+  let mut data = base58::decode_check(&xpub.to_string()).expect("Invalid Base58Check xpub");
+
+  // Substitui version bytes
+  data[0..4].copy_from_slice(&version.to_be_bytes());
+
+  // Recalcula checksum
+  let checksum = sha256d::Hash::hash(&data[..data.len() - 4]);
+
+  let len = data.len();
+  data[len - 4..len].copy_from_slice(&checksum[..4]);
+
+  // Encode Base58Check
+  base58::encode_check(&data)
+}
+
+pub fn xpub_to_ypub(xpub: &Xpub) -> String {
+  convert_xpub_prefix(xpub, 0x049d7cb2) // ypub
+}
+
+/// Re-encodes an xprv with another version prefix. Both the serialized key
+/// and the returned string are wiped on drop.
+pub fn convert_xprv_prefix(xprv: &Xpriv, version: u32) -> Zeroizing<String> {
+  let mut data = Zeroizing::new(xprv.encode());
+  data[0..4].copy_from_slice(&version.to_be_bytes());
+  Zeroizing::new(base58::encode_check(&data[..]))
+}
+
+/// Extended private key whose key material is overwritten on drop.
+///
+/// `Xpriv` is `Copy`, so this is best effort: it wipes the copy it owns,
+/// not temporaries the compiler may have made along the way.
+pub struct SecretXpriv(pub Xpriv);
+
+impl Deref for SecretXpriv {
+  type Target = Xpriv;
+
+  fn deref(&self) -> &Xpriv {
+    &self.0
+  }
+}
+
+impl Drop for SecretXpriv {
+  fn drop(&mut self) {
+    self.0.private_key.non_secure_erase();
+    self.0.chain_code = ChainCode::from([0u8; 32]);
+  }
+}
+
+/// Encodes an account xpub with the SLIP-132 prefix used by `script_type`
+/// (zpub/vpub for BIP84, ypub/upub for BIP49, plain xpub/tpub for BIP44 and
+/// BIP86, as SLIP-132 defines no Taproot prefix).
+pub fn slip132_xpub(xpub: &Xpub, script_type: ScriptType) -> String {
+  match (script_type, xpub.network.is_mainnet()) {
+    (ScriptType::Bip84, true) => xpub_to_zpub(xpub),
+    (ScriptType::Bip84, false) => convert_xpub_prefix(xpub, 0x045f1cf6), // vpub
+    (ScriptType::Bip49, true) => xpub_to_ypub(xpub),
+    (ScriptType::Bip49, false) => convert_xpub_prefix(xpub, 0x044a5262), // upub
+    (ScriptType::Bip44 | ScriptType::Bip86, _) => xpub.to_string(),
+  }
+}
+
+/// Encodes an account xprv with the SLIP-132 prefix used by `script_type`.
+pub fn slip132_xprv(xprv: &Xpriv, script_type: ScriptType) -> Zeroizing<String> {
+  match (script_type, xprv.network.is_mainnet()) {
+    (ScriptType::Bip84, true) => xprv_to_zprv(xprv),
+    (ScriptType::Bip84, false) => convert_xprv_prefix(xprv, 0x045f18bc), // vprv
+    (ScriptType::Bip49, true) => xprv_to_yprv(xprv),
+    (ScriptType::Bip49, false) => convert_xprv_prefix(xprv, 0x044a4e28), // uprv
+    (ScriptType::Bip44 | ScriptType::Bip86, true) => convert_xprv_prefix(xprv, 0x0488ade4), // xprv
+    (ScriptType::Bip44 | ScriptType::Bip86, false) => convert_xprv_prefix(xprv, 0x04358394), // tprv
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn descriptor_checksum_vectors() {
+    // BIP380
+    assert_eq!(descriptor_checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+    // Bitcoin Core doc/descriptors.md
+    assert_eq!(
+      descriptor_checksum(
+        "wpkh([d34db33f/84h/0h/0h]xpub6DJ2dNUysrn5Vt36jH2KLBT2i1auw1tTSSomg8PhqNiUtx8QX2SvC9nrHu81fT41fvDUnhMjEzQgXnQjKEu3oaqMSzhSrHMxyyoEAmUHQbY/0/*)"
+      )
+      .unwrap(),
+      "cjjspncu"
+    );
+    // Characters outside the descriptor charset
+    assert_eq!(descriptor_checksum("raw(deadbeef)\u{e9}"), None);
+  }
+
+  #[test]
+  fn output_descriptor_layout() {
+    let key_origin = format_key_origin([0x73, 0xc5, 0xda, 0x0a], 86, 0, 0);
+    assert_eq!(key_origin, "[73c5da0a/86h/0h/0h]");
+    let descriptor = output_descriptor(86, &key_origin, "xpub", 1);
+    let (body, checksum) = descriptor.split_once('#').unwrap();
+    assert_eq!(body, "tr([73c5da0a/86h/0h/0h]xpub/1/*)");
+    assert_eq!(descriptor_checksum(body).unwrap(), checksum);
+  }
+}
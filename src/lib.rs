@@ -0,0 +1,21 @@
+// Author: William C. Canin <https://williamcanin.github.io>
+
+//! Wallet derivation used by the `seedctl` binary.
+//!
+//! The binary only collects inputs (dice, passphrase, network, ...) and
+//! prints results; everything from entropy to the exported JSON lives here,
+//! so other tools can depend on exactly the same derivation.
+
+pub mod encryption;
+pub mod entropy;
+pub mod export;
+pub mod hardening;
+pub mod keys;
+pub mod meta;
+pub mod mnemonic;
+pub mod offline;
+pub mod passphrase;
+pub mod seedqr;
+pub mod sheet;
+pub mod slip39;
+pub mod wallet;
@@ -1,26 +1,42 @@
 // Author: William C. Canin <https://williamcanin.github.io>
 
 mod args;
-mod export;
-mod meta;
 mod slogan;
 mod utils;
+mod viewer;
 
-use bip39::Mnemonic;
+use args::{DecryptOptions, DiceMode, RunOptions};
+use bip39::{Language, Mnemonic};
 use console::style;
-use dialoguer::{Confirm, Input, Select};
-use std::{error::Error, fs};
+use dialoguer::{Confirm, Input, MultiSelect, Password};
+use std::{
+  error::Error,
+  fs,
+  io::{self, IsTerminal, Write},
+  path::{Path, PathBuf},
+};
+use viewer::{DisplayMode, Secret, Section};
+use zeroize::Zeroizing;
 
-use bitcoin::{
-  Address, Network,
-  bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
-  secp256k1::Secp256k1,
+use bitcoin::Network;
+use seedctl::{
+  encryption,
+  entropy::{self, DiceOrigin, DiceScheme, DiceStats, EntropySource},
+  export::ExportFormat,
+  hardening, mnemonic,
+  passphrase::{self, Rating},
+  seedqr::{self, SeedQrFormat},
+  sheet::{self, SheetMode},
+  slip39::{self, SharingSpec},
+  wallet::{self, AddressChain, AddressRange, ScriptType, WalletParams, WalletSeed},
 };
-use serde_json::to_string_pretty;
 
 fn main() -> Result<(), Box<dyn Error>> {
+  // PROCESS PROTECTION — before any secret is read
+  let memory = hardening::protect_process();
+
   // FLAGS
-  match args::parse_args() {
+  let opts = match args::parse_args()? {
     args::CliAction::Version => {
       args::print_version();
       return Ok(());
@@ -29,342 +45,1269 @@ fn main() -> Result<(), Box<dyn Error>> {
       args::print_about();
       return Ok(());
     }
-    args::CliAction::Run => {}
-  }
+    args::CliAction::Decrypt(opts) => return decrypt_export(&opts),
+    args::CliAction::Run(opts) => opts,
+  };
 
   // SLOGAN
   utils::slogan(true, true);
 
   // SECURITY CHECK — MUST BE FIRST
-  // utils::ensure_offline();
+  utils::ensure_offline(opts.offline);
 
   // SECURITY CARD — CONFIRM TO PROCEED
-  let confirmed = utils::show_important_card_with_confirm()?;
+  let confirmed = if opts.yes {
+    utils::show_important_card();
+    true
+  } else {
+    utils::show_important_card_with_confirm()?
+  };
   if !confirmed {
     eprintln!("Error: User did not confirm reading the recommendations.");
     utils::copyright_bottom();
     std::process::exit(1);
   }
 
-  // MNEMONIC SIZE
-  let mnemonic_choice = Select::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("[ Mnemonic size (seed) ]")
-    .items(["12 words (128 bits)", "24 words (256 bits)"])
-    .default(0)
-    .interact()
-    .unwrap();
+  // SEED SOURCE
+  let seed_source = if opts.mnemonic_file.is_some() {
+    SeedSource::Import
+  } else if opts.slip39_shares.is_some() {
+    SeedSource::Slip39
+  } else if opts.uses_dice() {
+    SeedSource::Dice
+  } else {
+    match utils::select(
+      opts.yes,
+      "[ Seed source ]",
+      &[
+        "Generate new mnemonic (dice)",
+        "Import existing mnemonic (BIP39)",
+        "Recover from SLIP-39 shares",
+      ],
+    ) {
+      0 => SeedSource::Dice,
+      1 => SeedSource::Import,
+      2 => SeedSource::Slip39,
+      _ => unreachable!(),
+    }
+  };
 
-  let bits = match mnemonic_choice {
-    0 => 128,
-    1 => 256,
-    _ => unreachable!(),
+  // SLIP-39 shares hold the BIP32 seed itself, without a mnemonic
+  let (mut seed, dice) = match seed_source {
+    SeedSource::Dice => {
+      let (mnemonic, dice) = mnemonic_from_dice(&opts, read_language(&opts))?;
+      (mnemonic_seed(mnemonic), Some(dice))
+    }
+    SeedSource::Import => (
+      mnemonic_seed(import_mnemonic(&opts, read_language(&opts))?),
+      None,
+    ),
+    SeedSource::Slip39 => (WalletSeed::MasterSecret(recover_slip39(&opts)?), None),
   };
+  if opts.seedqr.is_some() {
+    match seed.mnemonic() {
+      Some(mnemonic) => seedqr::check_supported(mnemonic)?,
+      None => return Err("SeedQR needs a mnemonic; SLIP-39 shares have none".into()),
+    }
+  }
 
-  let min_dice = utils::required_dice(bits);
-  println!("{} {} bits\n", style("Selected entropy:").bold(), bits);
+  // NETWORK
+  let network = match opts.network {
+    Some(network) => network,
+    None => match utils::select(
+      opts.yes,
+      "Network",
+      &["Bitcoin (Mainnet)", "Bitcoin (Testnet)"],
+    ) {
+      0 => Network::Bitcoin,
+      1 => Network::Testnet,
+      _ => unreachable!(),
+    },
+  };
 
-  // DICE MODE
-  let dice_mode = Select::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("[ Dice (1-6) ]")
-    .items(["Auto (random)", "Manual (inform sequence)"])
-    .default(0)
-    .interact()
-    .unwrap();
+  // PASSPHRASE — a SLIP-39 passphrase was already applied to the shares
+  if let WalletSeed::Mnemonic {
+    mnemonic,
+    passphrase,
+  } = &mut seed
+  {
+    *passphrase = match &opts.passphrase_file {
+      Some(path) => {
+        let passphrase = args::read_passphrase_file(path)?;
+        if !passphrase.is_empty() {
+          print_passphrase_preview(mnemonic, &passphrase)?;
+        }
+        passphrase
+      }
+      None if opts.yes => Zeroizing::new(String::new()),
+      None => read_passphrase(mnemonic)?,
+    };
+  } else if opts.passphrase_file.is_some() {
+    return Err("--passphrase-file needs a mnemonic; use --slip39-passphrase-file".into());
+  }
 
-  let dice: Vec<u8> = match dice_mode {
-    0 => utils::generate_random_dice(min_dice),
-    1 => {
-      let dice = utils::read_manual_dice_with_feedback(bits);
+  // ADDRESS TYPE
+  let script_type = match opts.script_type {
+    Some(script_type) => script_type,
+    None => match utils::select(
+      opts.yes,
+      "Address type",
+      &[
+        "Native SegWit (BIP84) (recommended)",
+        "Nested SegWit (BIP49)",
+        "Legacy (BIP44)",
+        "Taproot (BIP86)",
+      ],
+    ) {
+      0 => ScriptType::Bip84,
+      1 => ScriptType::Bip49,
+      2 => ScriptType::Bip44,
+      3 => ScriptType::Bip86,
+      _ => unreachable!(),
+    },
+  };
 
-      if dice.len() < min_dice {
-        panic!(
-          "Insufficient data: {} provided, minimum {}",
-          dice.len(),
-          min_dice
-        );
-      }
+  // ACCOUNTS
+  let accounts = match &opts.accounts {
+    Some(accounts) => accounts.clone(),
+    None if opts.yes => 0..=0,
+    None => Input::<String>::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Account index (e.g. 0 or 0-2)")
+      .default("0".to_string())
+      .validate_with(|input: &String| {
+        args::parse_account_range(input)
+          .map(|_| ())
+          .map_err(|e| e.to_string())
+      })
+      .interact_text()
+      .map(|input| args::parse_account_range(&input).unwrap())
+      .unwrap(),
+  };
 
-      dice
+  // ADDRESSES
+  let address_range = if opts.has_address_range() || opts.yes {
+    AddressRange {
+      start: opts.address_start.unwrap_or(0),
+      count: opts.address_count.unwrap_or(wallet::DEFAULT_ADDRESS_COUNT),
+      chain: opts.address_chain.unwrap_or(AddressChain::Receive),
     }
-    _ => unreachable!(),
+  } else {
+    read_address_range()
   };
 
-  // VISUAL CONFIRMATION
-  let dice_str: String = dice.iter().map(|d| char::from(b'0' + d)).collect();
-  println!("{} {}\n", style("DICE USED:").bold().yellow(), dice_str);
+  if seed.mnemonic().is_some() {
+    println!(
+      "{} {}",
+      style("Mnemonic checksum:").bold(),
+      style("valid (BIP39)").green()
+    );
+  }
 
-  if !Confirm::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("Please confirm that the above information is correct.")
-    .interact()
-    .unwrap()
+  let wallets = accounts
+    .map(|account| {
+      wallet::derive_wallet(WalletParams {
+        seed: seed.clone(),
+        network,
+        script_type,
+        account,
+        addresses: address_range,
+        dice,
+      })
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  let multiple = wallets.len() > 1;
+
+  // ENCRYPTION — one password for every file written, asked when the first
+  // one is
+  let mut password = ExportPassword::new(&opts)?;
+
+  // DISPLAY MODE
+  let display = match opts.display {
+    Some(mode) => mode,
+    None if opts.yes => DisplayMode::Plain,
+    None => {
+      let labels: Vec<&str> = DisplayMode::ALL.iter().map(|m| m.label()).collect();
+      DisplayMode::ALL[utils::select(false, "[ Display secrets ]", &labels)]
+    }
+  };
+  if display.is_secure() && !io::stdout().is_terminal() {
+    return Err("The secure viewer needs a terminal; use --display plain".into());
+  }
+
+  // The secure viewer shows the SeedQR, so it is chosen up front
+  let mnemonic = seed.mnemonic();
+  let mut qr_format = None;
+  if let Some(mnemonic) = mnemonic
+    && display.is_secure()
   {
-    panic!("Aborted by the user");
+    qr_format = read_seedqr_format(&opts, mnemonic);
   }
 
-  // NETWORK
-  let network_choice = Select::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("Network")
-    .items(["Bitcoin (Mainnet)", "Bitcoin (Testnet)"])
-    .default(0)
-    .interact()
-    .unwrap();
+  // ENVIRONMENT CHECK — before any secret is displayed
+  utils::check_environment(opts.yes, memory)?;
 
-  let (network, coin_type) = match network_choice {
-    0 => (Network::Bitcoin, 0),
-    1 => (Network::Testnet, 1),
-    _ => unreachable!(),
-  };
+  // SECURE VIEWER — mnemonic, SeedQR and private keys, then the screen and
+  // scrollback are wiped
+  if display.is_secure() {
+    let mut sections = Vec::new();
+    if let Some(mnemonic) = mnemonic {
+      sections.push(Section {
+        title: format!("Mnemonic ({} words)", mnemonic.word_count()),
+        items: mnemonic
+          .words()
+          .zip(mnemonic.word_indices())
+          .enumerate()
+          // The index identifies the word, so it is hidden along with it
+          .map(|(i, (word, idx))| Secret {
+            label: format!("{:02}.", i + 1),
+            value: Zeroizing::new(format!("{:04}  {}", idx + 1, word)),
+          })
+          .collect(),
+      });
+      if let Some(format) = qr_format {
+        sections.push(Section {
+          title: format.label().to_string(),
+          items: vec![Secret {
+            label: "Scan with the signing device:".to_string(),
+            value: seedqr::render_terminal(&seedqr::encode(mnemonic, format)?),
+          }],
+        });
+      }
+    }
+    sections.push(Section {
+      title: "Account Private Key".to_string(),
+      items: wallets
+        .iter()
+        .map(|wallet| Secret {
+          label: format!("Account #{}", wallet.account),
+          value: wallet.slip132_xprv(),
+        })
+        .collect(),
+    });
+    viewer::show(display, &sections)?;
+  }
 
-  // PASSPHRASE
-  let passphrase_title = style("[Optional] Passphrase (enter = empty)")
-    .bold()
-    .yellow()
-    .to_string();
+  // OUTPUT / YOUR WALLET
+  println!(
+    "\n\n{}\n",
+    style(format!("Your wallet: {}", "-".repeat(46)))
+      .bold()
+      .blue()
+  );
 
-  let passphrase: String = Input::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt(passphrase_title)
-    .allow_empty(true)
-    .interact_text()
-    .unwrap();
+  match mnemonic {
+    None => println!(
+      "{} none, the wallet comes from a SLIP-39 master secret",
+      style("Mnemonic:").bold()
+    ),
+    Some(mnemonic) if display.is_secure() => println!(
+      "{} shown in the secure viewer ({} words); the screen was cleared",
+      style("Mnemonic:").bold(),
+      mnemonic.word_count()
+    ),
+    Some(mnemonic) => {
+      println!("{}\n", style("POSITION  INDEXES  SEED").bold());
 
-  // CRYPTO CORE (FIXED)
-  let dice_entropy = utils::dice_hash(&dice);
-  let final_entropy = match dice_mode {
-    // Auto → Hybrid
-    0 => {
-      let system_entropy = utils::generate_system_entropy(32);
-      let combined = utils::combine_entropy(&dice_entropy, &system_entropy);
+      let words = mnemonic.words();
+      let indices = mnemonic.word_indices();
+
+      for (i, (word, idx)) in words.zip(indices).enumerate() {
+        println!(
+          "{:02}.  {:04}  {}",
+          i + 1,
+          idx + 1,
+          style(word).bold().yellow()
+        );
+      }
+
+      qr_format = read_seedqr_format(&opts, mnemonic);
+    }
+  }
+
+  if let (Some(mnemonic), Some(format)) = (mnemonic, qr_format) {
+    show_seedqr(
+      &opts,
+      mnemonic,
+      format,
+      &wallets[0].fingerprint.to_string(),
+      !display.is_secure(),
+      &mut password,
+    )?;
+  }
+
+  println!(
+    "\n{} {}",
+    style("Master fingerprint:").bold(),
+    wallets[0].fingerprint
+  );
+
+  for wallet in &wallets {
+    if multiple {
       println!(
-        "{}",
-        style("Entropy mode: HYBRID (dice + system RNG)").bold()
+        "\n{}",
+        style(format!("Account #{} {}", wallet.account, "-".repeat(40))).bold()
       );
-      utils::truncate_entropy(&combined, bits)
     }
 
-    //  Manual → Deterministic
-    1 => {
+    println!(
+      "{} {}",
+      style("\nDerivation path:").bold(),
+      wallet.derivation_path_string()
+    );
+
+    if display.is_secure() {
       println!(
-        "{}",
-        style("Entropy mode: DETERMINISTIC (dice only)").bold()
+        "{} shown in the secure viewer",
+        style("\nAccount Private Key:").bold()
+      );
+    } else {
+      println!(
+        "{} {}",
+        style("\nAccount Private Key:").bold(),
+        wallet.slip132_xprv().as_str()
       );
-      utils::truncate_entropy(&dice_entropy, bits)
     }
 
-    _ => unreachable!(),
-  };
+    println!(
+      "{} {}",
+      style("\nAccount Public Key:").bold(),
+      wallet.slip132_xpub()
+    );
 
-  // ADDRESS TYPE
-  let address_type = Select::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("Address type")
-    .items([
-      "Native SegWit (BIP84) (recommended)",
-      "Nested SegWit (BIP49)",
-      "Legacy (BIP44)",
-    ])
-    .default(0)
-    .interact()
-    .unwrap();
+    println!("\n{}", style("Output Descriptor (receive):").bold());
+    println!("{}", wallet.descriptor_receive);
 
-  let (purpose, addr_label) = match address_type {
-    0 => (84, "Address BIP84 (Native SegWit)"),
-    1 => (49, "Address BIP49 (Nested SegWit)"),
-    2 => (44, "Address BIP44 (Legacy)"),
-    _ => unreachable!(),
-  };
+    println!("\n{}", style("Output Descriptor (change):").bold());
+    println!("{}", wallet.descriptor_change);
 
-  let mnemonic = Mnemonic::from_entropy(&final_entropy).unwrap();
-  println!(
-    "{} {}",
-    style("Mnemonic checksum:").bold(),
-    style("valid (BIP39)").green()
-  );
-  let words: Vec<&str> = mnemonic.words().collect();
-  let indices = mnemonic.word_indices();
-  let seed = mnemonic.to_seed(&passphrase);
+    println!("\n{}", style(script_type.address_label()).bold());
 
-  let secp = Secp256k1::new();
-  let master = Xpriv::new_master(network, &seed).unwrap();
+    if wallet.addresses.is_empty() {
+      println!("(none)");
+    }
 
-  let path: DerivationPath = format!("m/{}'/{}'/0'", purpose, coin_type).parse().unwrap();
-  let acc_xprv = master.derive_priv(&secp, &path).unwrap();
-  let acc_xpub = Xpub::from_priv(&secp, &acc_xprv);
+    for addr in &wallet.addresses {
+      println!("{} → {}", addr.path, addr.address);
+    }
+  }
 
-  let account_xpub = match address_type {
-    0 => utils::xpub_to_zpub(&acc_xpub), // BIP84
-    1 => utils::xpub_to_ypub(&acc_xpub), // BIP49
-    2 => acc_xpub.to_string(),           // BIP44 (xpub)
-    _ => unreachable!(),
+  // SLIP-39 SHARES
+  let sharing = match &opts.slip39 {
+    Some(spec) => Some(spec.clone()),
+    None if opts.yes => None,
+    None => read_sharing_spec(),
   };
+  if let Some(spec) = sharing {
+    print_slip39_shares(&seed, &spec, display)?;
+  }
 
-  let account_xprv = match address_type {
-    0 => utils::xprv_to_zprv(&acc_xprv), // BIP84 → zprv
-    1 => utils::xprv_to_yprv(&acc_xprv), // BIP49 → yprv
-    2 => acc_xprv.to_string(),           // BIP44 → xprv
-    _ => unreachable!(),
+  println!("\n{}\n", style("-".repeat(60)).bold().blue());
+
+  // EXPORT WATCH ONLY
+  // Explicit paths are used as given; `None` means "default file name"
+  let mut exports: Vec<(ExportFormat, Option<PathBuf>)> = if !opts.exports.is_empty() {
+    opts
+      .exports
+      .iter()
+      .map(|(format, path)| (*format, Some(path.clone())))
+      .collect()
+  } else if opts.yes {
+    // Unattended runs only export when a path is given
+    Vec::new()
+  } else {
+    let labels: Vec<&str> = ExportFormat::WATCH_ONLY.iter().map(|f| f.label()).collect();
+    MultiSelect::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Export watch-only wallet? (space = select, enter = confirm)")
+      .items(&labels)
+      .defaults(&[true])
+      .interact()
+      .unwrap()
+      .into_iter()
+      .map(|i| (ExportFormat::WATCH_ONLY[i], None))
+      .collect()
   };
 
-  let fingerprint = master.fingerprint(&secp);
-  let fingerprint_bytes = [
-    fingerprint[0],
-    fingerprint[1],
-    fingerprint[2],
-    fingerprint[3],
-  ];
+  // EXPORT PRIVATE KEY — its own question, never selected by default
+  if opts.exports.is_empty()
+    && !opts.yes
+    && Confirm::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Also export the account private key?")
+      .default(false)
+      .interact()
+      .unwrap()
+  {
+    exports.push((ExportFormat::SeedctlPrivate, None));
+  }
+  if exports
+    .iter()
+    .any(|(format, _)| *format == ExportFormat::SeedctlPrivate)
+    && !confirm_private_export(opts.yes)
+  {
+    exports.retain(|(format, _)| *format != ExportFormat::SeedctlPrivate);
+    println!("{}", style("Private key export skipped.").yellow());
+  }
 
-  let key_origin = utils::format_key_origin(fingerprint_bytes, purpose, coin_type);
+  // Encryption is suggested when the private key is among the exports
+  let private = exports
+    .iter()
+    .any(|(format, _)| *format == ExportFormat::SeedctlPrivate);
 
-  let desc_receive = utils::output_descriptor(purpose, &key_origin, &account_xpub, 0);
+  for wallet in &wallets {
+    for (format, path) in &exports {
+      let path = match path {
+        Some(path) if multiple => utils::account_file_path(path, wallet.account),
+        Some(path) => path.clone(),
+        None => {
+          PathBuf::from(format.default_file_name(&wallet.fingerprint.to_string(), wallet.account))
+        }
+      };
 
-  let desc_change = utils::output_descriptor(purpose, &key_origin, &account_xpub, 1);
+      let contents = match wallet.render_export(*format) {
+        Ok(contents) => contents,
+        Err(e) => {
+          eprintln!("{} {}", style("Skipped:").bold().yellow(), e);
+          continue;
+        }
+      };
+      write_export(&path, contents.as_bytes(), password.get(private)?)?;
+    }
+  }
 
-  // OUTPUT / YOUR WALLET
-  println!(
-    "\n\n{}\n",
-    style(format!("Your wallet: {}", "-".repeat(46)))
-      .bold()
-      .blue()
+  // BACKUP SHEET — only the public sheet when there are no words to print
+  let default_mode = if mnemonic.is_some() {
+    SheetMode::Full
+  } else {
+    SheetMode::Public
+  };
+  let sheet = match &opts.backup_sheet {
+    Some(path) => Some((path.clone(), opts.backup_sheet_mode.unwrap_or(default_mode))),
+    None if opts.yes => None,
+    None => read_backup_sheet(
+      &opts,
+      &wallets[0].fingerprint.to_string(),
+      mnemonic.is_some(),
+    ),
+  };
+  let passphrase_used = matches!(
+    &seed,
+    WalletSeed::Mnemonic { passphrase, .. } if !passphrase.is_empty()
   );
+  if let Some((path, mode)) = sheet {
+    for wallet in &wallets {
+      let path = if multiple {
+        utils::account_file_path(&path, wallet.account)
+      } else {
+        path.clone()
+      };
+      let contents = match path.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("pdf") => {
+          sheet::render_pdf(wallet, mode, passphrase_used)?
+        }
+        _ => sheet::render_svg(wallet, mode, passphrase_used)?,
+      };
+      write_export(&path, &contents, password.get(mode == SheetMode::Full)?)?;
+    }
+  }
+  println!();
 
-  println!("{}\n", style("POSITION  INDEXES  SEED").bold());
+  utils::copyright_bottom();
+  if !opts.yes {
+    utils::exit_confirm();
+  }
 
-  for (i, (word, idx)) in words.iter().zip(indices).enumerate() {
-    println!(
-      "{:02}.  {:04}  {}",
-      i + 1,
-      idx + 1,
-      style(word).bold().yellow()
+  Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum SeedSource {
+  Dice,
+  Import,
+  Slip39,
+}
+
+/// Masked passphrase entry, typed twice, followed by a preview of the
+/// wallet it opens. Rejecting the preview asks for the passphrase again.
+fn read_passphrase(mnemonic: &Mnemonic) -> Result<Zeroizing<String>, Box<dyn Error>> {
+  let title = style("[Optional] Passphrase (enter = empty)")
+    .bold()
+    .yellow()
+    .to_string();
+
+  loop {
+    let passphrase = Zeroizing::new(
+      Password::with_theme(&utils::dialoguer_theme("►"))
+        .with_prompt(&title)
+        .with_confirmation("Repeat the passphrase", "The passphrases do not match")
+        .allow_empty_password(true)
+        .interact()
+        .unwrap(),
     );
+    if passphrase.is_empty() {
+      return Ok(passphrase);
+    }
+
+    print_passphrase_preview(mnemonic, &passphrase)?;
+    if Confirm::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Continue with this passphrase?")
+      .default(true)
+      .interact()
+      .unwrap()
+    {
+      return Ok(passphrase);
+    }
   }
+}
 
-  // println!("{} {}", style("\nDerivation path:").bold(), path);
-  println!(
-    "{} m/{}'/{}'/0'",
-    style("\nDerivation path:").bold(),
-    purpose,
-    coin_type
-  );
+/// Master fingerprints with and without the passphrase, so a known wallet
+/// can be recognised before anything else is derived, and its strength.
+fn print_passphrase_preview(mnemonic: &Mnemonic, passphrase: &str) -> Result<(), Box<dyn Error>> {
+  let without = wallet::master_fingerprint(mnemonic, "")?;
+  let with = wallet::master_fingerprint(mnemonic, passphrase)?;
+  let strength = passphrase::estimate(passphrase);
+  let rating = match strength.rating {
+    Rating::VeryWeak | Rating::Weak => style(strength.rating.label()).red(),
+    Rating::Fair => style(strength.rating.label()).yellow(),
+    Rating::Strong | Rating::VeryStrong => style(strength.rating.label()).green(),
+  };
 
   println!(
-    "\n{} {:02x}{:02x}{:02x}{:02x}",
-    style("Master fingerprint:").bold(),
-    fingerprint[0],
-    fingerprint[1],
-    fingerprint[2],
-    fingerprint[3],
+    "\n{} {}",
+    style("Master fingerprint without passphrase:").bold(),
+    without
   );
-
   println!(
     "{} {}",
-    style("\nAccount Private Key:").bold(),
-    account_xprv
+    style("Master fingerprint with passphrase:   ").bold(),
+    style(with).bold().yellow()
   );
+  println!(
+    "{} ~{:.0} bits, {} (rough estimate)",
+    style("Passphrase strength:").bold(),
+    strength.bits,
+    rating
+  );
+  if passphrase.trim() != passphrase {
+    println!(
+      "{}",
+      style("⚠ The passphrase starts or ends with whitespace, which is part of it.").yellow()
+    );
+  }
+  println!();
+  Ok(())
+}
 
-  println!("{} {}", style("\nAccount Public Key:").bold(), account_xpub);
+/// Asks whether to encrypt the saved files, with `default` as the suggested
+/// answer (yes when the first file holds a secret), then for the password.
+fn read_export_password(default: bool) -> Result<Option<Zeroizing<String>>, Box<dyn Error>> {
+  if !Confirm::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Encrypt the saved files with a password?")
+    .default(default)
+    .interact()?
+  {
+    return Ok(None);
+  }
 
-  println!("\n{}", style("Output Descriptor (receive):").bold());
-  println!("{}", desc_receive);
+  // A failed prompt must not fall back to writing the files in plaintext
+  let password = Password::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Export password")
+    .with_confirmation("Repeat the password", "The passwords do not match")
+    .interact()?;
+  Ok(Some(Zeroizing::new(password)))
+}
+
+/// Password for every file written in this session: read from
+/// `--encrypt-password-file`, or asked the first time a file is written.
+struct ExportPassword {
+  asked: bool,
+  password: Option<Zeroizing<String>>,
+}
+
+impl ExportPassword {
+  fn new(opts: &RunOptions) -> Result<Self, Box<dyn Error>> {
+    Ok(match &opts.encrypt_password_file {
+      Some(path) => ExportPassword {
+        asked: true,
+        password: Some(args::read_password_file(path)?),
+      },
+      // Unattended runs only encrypt with a password file
+      None => ExportPassword {
+        asked: opts.yes,
+        password: None,
+      },
+    })
+  }
 
-  println!("\n{}", style("Output Descriptor (change):").bold());
-  println!("{}", desc_change);
+  /// The password, asking for it first if needed; `default` is the answer
+  /// suggested for whether to encrypt at all.
+  fn get(&mut self, default: bool) -> Result<Option<&str>, Box<dyn Error>> {
+    if !self.asked {
+      self.password = read_export_password(default)?;
+      self.asked = true;
+    }
+    Ok(self.password.as_deref().map(String::as_str))
+  }
+}
 
-  println!("\n{}", style(addr_label).bold());
+/// Writes an exported file, encrypted (with `.age` appended) when there is
+/// a password.
+fn write_export(
+  path: &Path,
+  contents: &[u8],
+  password: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+  match password {
+    Some(password) => {
+      let path = encryption::encrypted_path(path);
+      fs::write(&path, encryption::encrypt(contents, password)?)?;
+      println!(
+        "{} {} (encrypted)",
+        style("Exported:").bold(),
+        path.display()
+      );
+    }
+    None => {
+      fs::write(path, contents)?;
+      println!("{} {}", style("Exported:").bold(), path.display());
+    }
+  }
+  Ok(())
+}
 
-  for i in 0..10 {
-    let child = acc_xpub
-      .derive_pub(
-        &secp,
-        &[
-          ChildNumber::Normal { index: 0 },
-          ChildNumber::Normal { index: i },
-        ],
+fn decrypt_export(opts: &DecryptOptions) -> Result<(), Box<dyn Error>> {
+  let ciphertext =
+    fs::read(&opts.input).map_err(|e| format!("Unable to read {}: {}", opts.input.display(), e))?;
+  if !encryption::is_encrypted(&ciphertext) {
+    return Err(format!("{} is not an encrypted export", opts.input.display()).into());
+  }
+
+  let output = match &opts.output {
+    Some(path) => path.clone(),
+    None => encryption::decrypted_path(&opts.input)
+      .ok_or("The file has no .age extension; choose the output with --output")?,
+  };
+  let to_stdout = output == Path::new("-");
+  if !to_stdout && output.exists() {
+    return Err(
+      format!(
+        "{} already exists; choose another --output",
+        output.display()
       )
-      .unwrap();
+      .into(),
+    );
+  }
 
-    let addr = match address_type {
-      // BIP84 – Native SegWit
-      0 => Address::p2wpkh(&bitcoin::CompressedPublicKey(child.public_key), network),
+  let password = match &opts.password_file {
+    Some(path) => args::read_password_file(path)?,
+    None => Zeroizing::new(
+      Password::with_theme(&utils::dialoguer_theme("►"))
+        .with_prompt("Export password")
+        .interact()
+        .unwrap(),
+    ),
+  };
+  let plaintext = encryption::decrypt(&ciphertext, &password)?;
 
-      // BIP49 – Nested SegWit
-      1 => Address::p2shwpkh(&bitcoin::CompressedPublicKey(child.public_key), network),
+  if to_stdout {
+    std::io::stdout().write_all(&plaintext)?;
+  } else {
+    fs::write(&output, &*plaintext)?;
+    println!("{} {}", style("Decrypted:").bold(), output.display());
+  }
+  Ok(())
+}
 
-      // BIP44 – Legacy
-      2 => {
-        let pk = bitcoin::PublicKey::new(child.public_key);
-        Address::p2pkh(pk, network)
-      }
+/// Warns that the export can spend the funds and asks once more; with
+/// `--yes`, the explicit `--export-private` flag is the confirmation.
+fn confirm_private_export(yes: bool) -> bool {
+  println!(
+    "\n{}",
+    style("⚠ WARNING: this export contains the account private key.")
+      .bold()
+      .red()
+  );
+  println!(
+    "{}",
+    style("Anyone who reads the file can spend every coin of the account.").red()
+  );
+  println!(
+    "{}\n",
+    style("Keep it only on encrypted media that never touches an online machine.").red()
+  );
 
-      _ => unreachable!(),
-    };
+  yes
+    || Confirm::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Write the private key export?")
+      .default(false)
+      .interact()
+      .unwrap()
+}
 
-    println!("m/{}'/{}'/0'/0/{} → {}", purpose, coin_type, i, addr);
+fn read_address_range() -> AddressRange {
+  let choice = utils::select(
+    false,
+    "Addresses to show",
+    &[
+      "First 10 receive addresses",
+      "Custom range (start, count, receive/change)",
+    ],
+  );
+
+  if choice == 0 {
+    return AddressRange::default();
   }
 
-  let script_type = match purpose {
-    84 => "bip84",
-    49 => "bip49",
-    44 => "bip44",
+  let read_number = |prompt: &str, default: u32, max: u32| -> u32 {
+    Input::<u32>::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt(prompt)
+      .default(default)
+      .validate_with(|n: &u32| {
+        if *n <= max {
+          Ok(())
+        } else {
+          Err(format!("Expected a number from 0 to {}", max))
+        }
+      })
+      .interact_text()
+      .unwrap()
+  };
+
+  let start = read_number("First address index", 0, u32::MAX);
+  let count = read_number(
+    "Number of addresses",
+    wallet::DEFAULT_ADDRESS_COUNT,
+    wallet::MAX_ADDRESS_COUNT,
+  );
+  let chain = match utils::select(false, "Chain", &["Receive", "Change", "Both"]) {
+    0 => AddressChain::Receive,
+    1 => AddressChain::Change,
+    2 => AddressChain::Both,
     _ => unreachable!(),
   };
 
-  let watch_only = true; // ou true se remover xprv
+  AddressRange {
+    start,
+    count,
+    chain,
+  }
+}
 
-  let export = export::WalletExport {
-    software: export::SoftwareInfo {
-      name: "seedctl".to_string(),
-      version: meta::VERSION.to_string(),
-      repository: meta::PROJECT_REPOSITORY.to_string(),
-    },
-    network: match network {
-      Network::Bitcoin => "bitcoin".to_string(),
-      Network::Testnet => "testnet".to_string(),
-      _ => "unknown".to_string(),
-    },
-    script_type: script_type.to_string(),
-    key_origin: export::KeyOrigin {
-      fingerprint: format!(
-        "{:02x}{:02x}{:02x}{:02x}",
-        fingerprint[0], fingerprint[1], fingerprint[2], fingerprint[3],
-      ),
-      derivation_path: format!("m/{}'/{}'/0'", purpose, coin_type),
-    },
-    watch_only,
-    keys: export::Keys {
-      account_xpub: account_xpub.clone(),
-      account_xprv: if watch_only {
-        None
-      } else {
-        Some(account_xprv.clone())
-      },
-    },
-    descriptors: export::Descriptors {
-      receive: desc_receive,
-      change: desc_change,
+fn mnemonic_from_dice(
+  opts: &RunOptions,
+  language: Language,
+) -> Result<(Mnemonic, DiceOrigin), Box<dyn Error>> {
+  // MNEMONIC SIZE
+  let bits = match opts.bits {
+    Some(bits) => bits,
+    None => {
+      let labels: Vec<String> = mnemonic::VALID_WORD_COUNTS
+        .iter()
+        .map(|&words| format!("{} words ({} bits)", words, mnemonic::entropy_bits(words)))
+        .collect();
+      let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+      let words =
+        mnemonic::VALID_WORD_COUNTS[utils::select(opts.yes, "[ Mnemonic size (seed) ]", &labels)];
+      mnemonic::entropy_bits(words)
+    }
+  };
+
+  println!("{} {} bits\n", style("Selected entropy:").bold(), bits);
+
+  // DICE MODE
+  let dice_mode = match opts.dice_mode {
+    Some(mode) => mode,
+    None => match utils::select(
+      opts.yes,
+      "[ Dice ]",
+      &["Auto (random)", "Manual (inform sequence)"],
+    ) {
+      0 => DiceMode::Auto,
+      1 => DiceMode::Manual,
+      _ => unreachable!(),
     },
   };
 
-  let json = to_string_pretty(&export).unwrap();
+  // ENTROPY SOURCE (random rolls in auto mode are always d6)
+  let source = match opts.dice_source {
+    Some(source) => source,
+    None if dice_mode == DiceMode::Auto || opts.dice.is_some() => EntropySource::D6,
+    None => {
+      let labels: Vec<&str> = EntropySource::ALL.iter().map(|s| s.label()).collect();
+      EntropySource::ALL[utils::select(opts.yes, "[ Entropy source ]", &labels)]
+    }
+  };
+  let min_dice = source.required_rolls(bits);
 
-  // println!("\n{}", style("Export JSON:").bold());
-  // println!("{}", json);
+  // DICE SCHEME
+  let scheme = match opts.dice_scheme {
+    Some(scheme) if !scheme.supports(source) => {
+      return Err(format!("--dice-scheme {} only supports d6 rolls", scheme.name()).into());
+    }
+    Some(scheme) => scheme,
+    None => {
+      let schemes: Vec<DiceScheme> = DiceScheme::ALL
+        .into_iter()
+        .filter(|s| s.supports(source))
+        .collect();
+      let labels: Vec<&str> = schemes.iter().map(|s| s.label()).collect();
+      schemes[utils::select(opts.yes, "[ Dice to entropy ]", &labels)]
+    }
+  };
 
-  println!("\n{}\n", style("-".repeat(60)).bold().blue());
+  let dice: Zeroizing<Vec<u8>> = match dice_mode {
+    DiceMode::Auto => source.generate_random_rolls(min_dice),
+    DiceMode::Manual => {
+      let dice = match &opts.dice {
+        Some(dice) => dice.clone(),
+        None if opts.yes => return Err("Manual dice mode with --yes requires --dice".into()),
+        None => {
+          let dice = utils::read_manual_dice_with_feedback(source, bits);
+          // Double entry catches typos that a glance at the echo misses
+          if dice.len() >= min_dice
+            && Confirm::with_theme(&utils::dialoguer_theme("►"))
+              .with_prompt("Verify the rolls by typing them again?")
+              .default(true)
+              .interact()
+              .unwrap()
+          {
+            utils::verify_manual_dice(source, bits, dice)
+          } else {
+            dice
+          }
+        }
+      };
 
-  // EXPORT WATCH ONLY
-  let export_watch_only = Select::with_theme(&utils::dialoguer_theme("►"))
-    .with_prompt("Export watch-only wallet?")
-    .items(["Yes (xpub + fingerprint + path)", "No"])
-    .default(0)
+      if dice.len() < min_dice {
+        return Err(
+          format!(
+            "Insufficient data: {} provided, minimum {}",
+            dice.len(),
+            min_dice
+          )
+          .into(),
+        );
+      }
+
+      dice
+    }
+  };
+
+  // VISUAL CONFIRMATION
+  let dice_str = source.format_rolls(&dice);
+  println!(
+    "{} {}\n",
+    style("DICE USED:").bold().yellow(),
+    dice_str.as_str()
+  );
+
+  if dice_mode == DiceMode::Manual {
+    for warning in DiceStats::from_rolls(source, &dice).warnings {
+      println!("{} {}", style("⚠ Dice quality:").bold().yellow(), warning);
+    }
+  }
+
+  if !opts.yes
+    && !Confirm::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("Please confirm that the above information is correct.")
+      .interact()
+      .unwrap()
+  {
+    panic!("Aborted by the user");
+  }
+
+  // CRYPTO CORE (FIXED)
+  println!("{} {}", style("Entropy source:").bold(), source.label());
+  println!("{} {}", style("Dice scheme:").bold(), scheme.label());
+  let dice_entropy = scheme.entropy(source, &dice, bits);
+  let final_entropy = match dice_mode {
+    // Auto → Hybrid
+    DiceMode::Auto => {
+      let system_entropy = entropy::generate_system_entropy(32);
+      let combined = entropy::combine_entropy(&dice_entropy, &system_entropy);
+      println!(
+        "{}",
+        style("Entropy mode: HYBRID (dice + system RNG)").bold()
+      );
+      entropy::truncate_entropy(&combined, bits)
+    }
+
+    //  Manual → Deterministic
+    DiceMode::Manual => {
+      println!(
+        "{}",
+        style("Entropy mode: DETERMINISTIC (dice only)").bold()
+      );
+      entropy::truncate_entropy(&dice_entropy, bits)
+    }
+  };
+
+  let origin = DiceOrigin {
+    source,
+    scheme,
+    hybrid: dice_mode == DiceMode::Auto,
+    rolls: dice.len(),
+  };
+
+  Ok((Mnemonic::from_entropy_in(language, &final_entropy)?, origin))
+}
+
+fn read_language(opts: &RunOptions) -> Language {
+  match opts.language {
+    Some(language) => language,
+    None => {
+      let labels: Vec<&str> = mnemonic::LANGUAGES
+        .iter()
+        .map(|l| mnemonic::language_label(*l))
+        .collect();
+      mnemonic::LANGUAGES[utils::select(opts.yes, "[ Wordlist language ]", &labels)]
+    }
+  }
+}
+
+/// The passphrase is read later, once the network is known.
+fn mnemonic_seed(mnemonic: Mnemonic) -> WalletSeed {
+  WalletSeed::Mnemonic {
+    mnemonic,
+    passphrase: Zeroizing::new(String::new()),
+  }
+}
+
+fn import_mnemonic(opts: &RunOptions, language: Language) -> Result<Mnemonic, Box<dyn Error>> {
+  let phrase: Zeroizing<String> = match &opts.mnemonic_file {
+    Some(path) => Zeroizing::new(
+      fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read mnemonic file {}: {}", path.display(), e))?,
+    ),
+    None if opts.yes => {
+      return Err("Importing a mnemonic with --yes requires --mnemonic-file".into());
+    }
+    None => Input::with_theme(&utils::dialoguer_theme("►"))
+      .with_prompt("[ Mnemonic words (separated by spaces) ]")
+      .validate_with(|input: &String| {
+        mnemonic::parse_mnemonic(input, language)
+          .map(|_| ())
+          .map_err(|e| e.to_string())
+      })
+      .interact_text()
+      .map(Zeroizing::new)
+      .unwrap(),
+  };
+
+  Ok(mnemonic::parse_mnemonic(&phrase, language).map_err(|e| e.to_string())?)
+}
+
+/// Returns the SLIP-39 master secret, which is used as the BIP32 seed.
+fn recover_slip39(opts: &RunOptions) -> Result<Zeroizing<Vec<u8>>, Box<dyn Error>> {
+  let shares: Vec<slip39::Share> = match &opts.slip39_shares {
+    Some(path) => {
+      let contents = Zeroizing::new(
+        fs::read_to_string(path)
+          .map_err(|e| format!("Unable to read shares file {}: {}", path.display(), e))?,
+      );
+      contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+          slip39::Share::parse(line)
+            .map_err(|e| format!("{} line {}: {}", path.display(), i + 1, e))
+        })
+        .collect::<Result<_, _>>()?
+    }
+    None if opts.yes => {
+      return Err("Recovering SLIP-39 shares with --yes requires --slip39-shares".into());
+    }
+    None => {
+      let mut shares = Vec::new();
+      loop {
+        let (complete, needed) = slip39::recovery_progress(&shares);
+        if !shares.is_empty() {
+          println!(
+            "{} {} of {} groups complete",
+            style("Shares:").bold(),
+            complete,
+            needed
+          );
+          if complete >= needed {
+            break;
+          }
+        }
+
+        let share = Input::<String>::with_theme(&utils::dialoguer_theme("►"))
+          .with_prompt(format!("[ SLIP-39 share #{} ]", shares.len() + 1))
+          .validate_with(|input: &String| match slip39::Share::parse(input) {
+            Ok(share) if shares.first().is_some_and(|first| !first.same_set(&share)) => {
+              Err("This share belongs to a different set".to_string())
+            }
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+          })
+          .interact_text()
+          .map(Zeroizing::new)
+          .unwrap();
+        shares.push(slip39::Share::parse(&share)?);
+      }
+      shares
+    }
+  };
+
+  let passphrase = match &opts.slip39_passphrase_file {
+    Some(path) => args::read_slip39_passphrase_file(path)?,
+    None if opts.yes => Zeroizing::new(String::new()),
+    None => Zeroizing::new(
+      Password::with_theme(&utils::dialoguer_theme("►"))
+        .with_prompt("[ SLIP-39 passphrase ] (empty if none)")
+        .allow_empty_password(true)
+        .interact()?,
+    ),
+  };
+
+  Ok(slip39::combine(&shares, passphrase.as_bytes())?)
+}
+
+fn read_sharing_spec() -> Option<SharingSpec> {
+  println!();
+  if !Confirm::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Split the seed into SLIP-39 shares?")
+    .default(false)
     .interact()
-    .unwrap();
+    .unwrap()
+  {
+    return None;
+  }
 
-  match export_watch_only {
-    0 => fs::write(format!("wallet-{}-watch-only.json", fingerprint), json)?,
-    1 => println!(),
-    _ => unreachable!(),
+  Input::<String>::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Shares (e.g. 2of3 or 2:2of3,3of5,1of1)")
+    .default("2of3".to_string())
+    .validate_with(|input: &String| {
+      SharingSpec::parse(input)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    })
+    .interact_text()
+    .map(|input| SharingSpec::parse(&input).unwrap())
+    .ok()
+}
+
+/// The shares hold the BIP32 seed, so what they restore differs from what a
+/// BIP39 user may expect.
+fn print_slip39_warning(message: &str) {
+  println!(
+    "\n{}",
+    style("⚠ WARNING: the SLIP-39 shares do not hold the mnemonic.")
+      .bold()
+      .yellow()
+  );
+  println!("{}", style(message).yellow());
+}
+
+/// Splits the BIP32 seed as the SLIP-39 master secret, so any SLIP-39
+/// implementation restores this exact wallet.
+fn print_slip39_shares(
+  seed: &WalletSeed,
+  spec: &SharingSpec,
+  display: DisplayMode,
+) -> Result<(), Box<dyn Error>> {
+  let secret = seed.to_seed();
+  let groups = slip39::split(&secret, spec, b"")?;
+  // A recovered master secret is shared as it is; a mnemonic wallet has only
+  // its 512-bit seed to share
+  let warning = seed.mnemonic().map(|_| {
+    "The shares hold the 512-bit BIP32 seed, with the BIP39 passphrase already \
+     applied. They restore this wallet but never give the mnemonic back, and \
+     wallets that only accept 128 or 256-bit secrets cannot import them."
+  });
+
+  if display.is_secure() {
+    let mut sections = Vec::new();
+    for (g, group) in groups.iter().enumerate() {
+      for (s, share) in group.shares.iter().enumerate() {
+        let title = if groups.len() > 1 {
+          format!(
+            "SLIP-39 ({}) group {} ({} of {}), share {}",
+            spec,
+            g + 1,
+            group.threshold,
+            group.shares.len(),
+            s + 1
+          )
+        } else {
+          format!("SLIP-39 ({}) share {}", spec, s + 1)
+        };
+        sections.push(Section {
+          title,
+          items: share
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| Secret {
+              label: format!("{:02}.", i + 1),
+              value: Zeroizing::new(word.to_string()),
+            })
+            .collect(),
+        });
+      }
+    }
+    viewer::show(display, &sections)?;
+    println!(
+      "\n{} shown in the secure viewer ({}); the screen was cleared",
+      style("SLIP-39 shares:").bold(),
+      spec
+    );
+    if let Some(warning) = warning {
+      print_slip39_warning(warning);
+    }
+    return Ok(());
+  }
+
+  println!(
+    "\n{}",
+    style(format!("SLIP-39 shares ({}) {}", spec, "-".repeat(40)))
+      .bold()
+      .blue()
+  );
+  if groups.len() > 1 {
+    println!(
+      "Any {} of the {} groups recover the seed.",
+      spec.group_threshold,
+      groups.len()
+    );
+  }
+
+  for (g, group) in groups.iter().enumerate() {
+    if groups.len() > 1 {
+      println!(
+        "\n{}",
+        style(format!(
+          "Group {} ({} of {} shares)",
+          g + 1,
+          group.threshold,
+          group.shares.len()
+        ))
+        .bold()
+      );
+    }
+    for (s, share) in group.shares.iter().enumerate() {
+      println!("\n{}", style(format!("Share {}:", s + 1)).bold());
+      let words: Vec<&str> = share.split_whitespace().collect();
+      // Four words per row, without building the share text again
+      for (i, word) in words.iter().enumerate() {
+        let end = i % 4 == 3 || i + 1 == words.len();
+        print!(
+          "{}{:02}. {}{}",
+          if i % 4 == 0 { "  " } else { "" },
+          i + 1,
+          style(word).bold().yellow(),
+          if end {
+            "\n".to_string()
+          } else {
+            " ".repeat(12 - word.len())
+          }
+        );
+      }
+    }
+  }
+
+  if let Some(warning) = warning {
+    print_slip39_warning(warning);
+  }
+  Ok(())
+}
+
+/// SeedQR format to show; only offered when the mnemonic fits the format.
+fn read_seedqr_format(opts: &RunOptions, mnemonic: &Mnemonic) -> Option<SeedQrFormat> {
+  match opts.seedqr {
+    Some(format) => Some(format),
+    None if opts.yes || seedqr::check_supported(mnemonic).is_err() => None,
+    None => {
+      println!();
+      let mut labels = vec!["Don't show"];
+      labels.extend(SeedQrFormat::ALL.iter().map(|f| f.label()));
+      match utils::select(false, "[ SeedQR ]", &labels) {
+        0 => None,
+        i => Some(SeedQrFormat::ALL[i - 1]),
+      }
+    }
+  }
+}
+
+/// Prints the SeedQR (unless the secure viewer already showed it) and
+/// offers to save it as an image.
+fn show_seedqr(
+  opts: &RunOptions,
+  mnemonic: &Mnemonic,
+  format: SeedQrFormat,
+  fingerprint: &str,
+  print_code: bool,
+  password: &mut ExportPassword,
+) -> Result<(), Box<dyn Error>> {
+  let code = seedqr::encode(mnemonic, format)?;
+  if print_code {
+    println!("\n{}\n", style(format!("{}:", format.label())).bold());
+    print!("{}", seedqr::render_terminal(&code).as_str());
+  }
+
+  let path = match &opts.seedqr_file {
+    Some(path) => Some(path.clone()),
+    None if opts.yes => None,
+    None => {
+      if Confirm::with_theme(&utils::dialoguer_theme("►"))
+        .with_prompt("Save the QR code as an image?")
+        .default(false)
+        .interact()
+        .unwrap()
+      {
+        Some(PathBuf::from(
+          Input::<String>::with_theme(&utils::dialoguer_theme("►"))
+            .with_prompt("Image file (.png or .svg)")
+            .default(format!("seedqr-{}.png", fingerprint))
+            .interact_text()
+            .unwrap(),
+        ))
+      } else {
+        None
+      }
+    }
   };
 
-  utils::copyright_bottom();
-  utils::exit_confirm();
+  if let Some(path) = path {
+    // The image holds the whole seed, so encryption is suggested
+    let image = match path.extension() {
+      Some(ext) if ext.eq_ignore_ascii_case("png") => seedqr::render_png(&code)?,
+      _ => Zeroizing::new(seedqr::render_svg(&code).as_bytes().to_vec()),
+    };
+    write_export(&path, &image, password.get(true)?)?;
+  }
 
   Ok(())
 }
+
+/// Without a mnemonic only the public sheet has something to print.
+fn read_backup_sheet(
+  opts: &RunOptions,
+  fingerprint: &str,
+  has_mnemonic: bool,
+) -> Option<(PathBuf, SheetMode)> {
+  if !Confirm::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Create a printable backup sheet?")
+    .default(false)
+    .interact()
+    .unwrap()
+  {
+    return None;
+  }
+
+  let mode = match opts.backup_sheet_mode {
+    Some(mode) => mode,
+    None if !has_mnemonic => SheetMode::Public,
+    None => {
+      let labels: Vec<&str> = SheetMode::ALL.iter().map(|m| m.label()).collect();
+      SheetMode::ALL[utils::select(false, "[ Backup sheet ]", &labels)]
+    }
+  };
+  let path = Input::<String>::with_theme(&utils::dialoguer_theme("►"))
+    .with_prompt("Sheet file (.pdf or .svg)")
+    .default(format!("backup-sheet-{}.pdf", fingerprint))
+    .interact_text()
+    .unwrap();
+
+  Some((PathBuf::from(path), mode))
+}
@@ -0,0 +1,316 @@
+use bip39::{Language, Mnemonic};
+use std::{borrow::Cow, fmt};
+use zeroize::Zeroizing;
+
+/// Word counts allowed by BIP39.
+pub const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Entropy size for a BIP39 word count: 32 bits per 3 words, so 12 words
+/// carry 128 bits and 24 words 256 bits.
+pub fn entropy_bits(word_count: usize) -> usize {
+  word_count / 3 * 32
+}
+
+/// Every wordlist shipped by the `bip39` crate, English first.
+pub const LANGUAGES: &[Language] = Language::ALL;
+
+/// Identifier used on the command line and in the export.
+pub fn language_name(language: Language) -> &'static str {
+  match language {
+    Language::English => "english",
+    Language::SimplifiedChinese => "chinese-simplified",
+    Language::TraditionalChinese => "chinese-traditional",
+    Language::Czech => "czech",
+    Language::French => "french",
+    Language::Italian => "italian",
+    Language::Japanese => "japanese",
+    Language::Korean => "korean",
+    Language::Portuguese => "portuguese",
+    Language::Spanish => "spanish",
+  }
+}
+
+pub fn language_label(language: Language) -> &'static str {
+  match language {
+    Language::English => "English",
+    Language::SimplifiedChinese => "Chinese (Simplified)",
+    Language::TraditionalChinese => "Chinese (Traditional)",
+    Language::Czech => "Czech",
+    Language::French => "French",
+    Language::Italian => "Italian",
+    Language::Japanese => "Japanese",
+    Language::Korean => "Korean",
+    Language::Portuguese => "Portuguese",
+    Language::Spanish => "Spanish",
+  }
+}
+
+pub fn language_from_name(name: &str) -> Option<Language> {
+  LANGUAGES
+    .iter()
+    .copied()
+    .find(|l| language_name(*l) == name)
+}
+
+#[derive(Debug)]
+pub enum MnemonicError {
+  WordCount(usize),
+  UnknownWord {
+    position: usize,
+    word: String,
+    language: Language,
+    suggestion: Option<&'static str>,
+  },
+  Checksum,
+}
+
+impl fmt::Display for MnemonicError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MnemonicError::WordCount(count) => write!(
+        f,
+        "Invalid word count: {} (expected 12, 15, 18, 21 or 24)",
+        count
+      ),
+      MnemonicError::UnknownWord {
+        position,
+        word,
+        language,
+        suggestion,
+      } => {
+        write!(
+          f,
+          "Word {:02} '{}' is not in the BIP39 {} wordlist",
+          position,
+          word,
+          language_label(*language)
+        )?;
+        if let Some(suggestion) = suggestion {
+          write!(f, " (did you mean '{}'?)", suggestion)?;
+        }
+        Ok(())
+      }
+      MnemonicError::Checksum => write!(
+        f,
+        "Invalid checksum: the words are valid but one of them is wrong or out of order"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for MnemonicError {}
+
+/// Splits and lowercases user input so that extra spaces, line breaks or
+/// capital letters do not make an otherwise valid mnemonic fail. Accented
+/// words are NFKD-normalized, the form the wordlists are stored in.
+pub fn normalize_words(input: &str) -> Zeroizing<Vec<String>> {
+  let mut input = Cow::Borrowed(input);
+  Mnemonic::normalize_utf8_cow(&mut input);
+  let words = input.split_whitespace().map(|w| w.to_lowercase()).collect();
+  if let Cow::Owned(normalized) = input {
+    drop(Zeroizing::new(normalized));
+  }
+  Zeroizing::new(words)
+}
+
+/// Validates an existing mnemonic in `language`: word count, wordlist
+/// membership (positions are 1-based) and BIP39 checksum.
+pub fn parse_mnemonic(input: &str, language: Language) -> Result<Mnemonic, MnemonicError> {
+  let words = normalize_words(input);
+
+  if !VALID_WORD_COUNTS.contains(&words.len()) {
+    return Err(MnemonicError::WordCount(words.len()));
+  }
+
+  if let Some((i, word)) = words
+    .iter()
+    .enumerate()
+    .find(|(_, w)| language.find_word(w).is_none())
+  {
+    return Err(MnemonicError::UnknownWord {
+      position: i + 1,
+      word: word.clone(),
+      language,
+      suggestion: suggest_word(language, word),
+    });
+  }
+
+  let phrase = Zeroizing::new(words.join(" "));
+  Mnemonic::parse_in_normalized(language, &phrase).map_err(|e| match e {
+    bip39::Error::BadWordCount(count) => MnemonicError::WordCount(count),
+    _ => MnemonicError::Checksum,
+  })
+}
+
+/// Nearest wordlist entry for a mistyped word. BIP39 words are unique by
+/// their first four letters, so a matching prefix wins; otherwise the word
+/// with the smallest edit distance is returned.
+pub fn suggest_word(language: Language, word: &str) -> Option<&'static str> {
+  if word.chars().count() >= 4 {
+    let prefix: String = word.chars().take(4).collect();
+    if let [only] = language.words_by_prefix(&prefix) {
+      return Some(only);
+    }
+  }
+
+  language
+    .word_list()
+    .iter()
+    .map(|candidate| (edit_distance(word, candidate), *candidate))
+    .filter(|(distance, _)| *distance <= 2)
+    .min_by_key(|(distance, _)| *distance)
+    .map(|(_, candidate)| candidate)
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent
+/// transpositions, the most common typo when copying words by hand.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+  let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+  for (i, row) in d.iter_mut().enumerate() {
+    row[0] = i;
+  }
+  for (j, cell) in d[0].iter_mut().enumerate() {
+    *cell = j;
+  }
+
+  for i in 1..=a.len() {
+    for j in 1..=b.len() {
+      let cost = usize::from(a[i - 1] != b[j - 1]);
+      d[i][j] = (d[i - 1][j] + 1)
+        .min(d[i][j - 1] + 1)
+        .min(d[i - 1][j - 1] + cost);
+      if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+        d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+      }
+    }
+  }
+
+  d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use unicode_normalization::UnicodeNormalization;
+
+  const VALID: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                       abandon abandon abandon about";
+
+  #[test]
+  fn parses_messy_input() {
+    let mnemonic = parse_mnemonic(
+      "  Abandon abandon ABANDON abandon\nabandon abandon abandon abandon\tabandon abandon abandon about ",
+      Language::English,
+    )
+    .unwrap();
+    assert_eq!(mnemonic.to_string(), VALID);
+  }
+
+  #[test]
+  fn rejects_bad_checksum() {
+    let words = ["abandon"; 12].join(" ");
+    assert!(matches!(
+      parse_mnemonic(&words, Language::English),
+      Err(MnemonicError::Checksum)
+    ));
+  }
+
+  #[test]
+  fn rejects_wrong_word_count() {
+    let words = ["abandon"; 11].join(" ");
+    assert!(matches!(
+      parse_mnemonic(&words, Language::English),
+      Err(MnemonicError::WordCount(11))
+    ));
+    assert!(matches!(
+      parse_mnemonic("", Language::English),
+      Err(MnemonicError::WordCount(0))
+    ));
+  }
+
+  #[test]
+  fn suggests_unknown_words() {
+    let words = VALID.replace("about", "abuot");
+    match parse_mnemonic(&words, Language::English) {
+      Err(MnemonicError::UnknownWord {
+        position,
+        word,
+        suggestion,
+        ..
+      }) => {
+        assert_eq!(position, 12);
+        assert_eq!(word, "abuot");
+        assert_eq!(suggestion, Some("about"));
+      }
+      other => panic!("unexpected result: {:?}", other.map(|m| m.to_string())),
+    }
+
+    // A unique four-letter prefix wins over the edit distance
+    assert_eq!(suggest_word(Language::English, "abandn"), Some("abandon"));
+    assert_eq!(suggest_word(Language::English, "zzzzzz"), None);
+  }
+
+  #[test]
+  fn edit_distance_counts_transpositions() {
+    assert_eq!(edit_distance("about", "about"), 0);
+    assert_eq!(edit_distance("abuot", "about"), 1);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+  }
+
+  #[test]
+  fn accepts_composed_accents() {
+    // Wordlists are stored NFKD; typed accents usually arrive composed (NFC)
+    let cases = [
+      (
+        Language::Spanish,
+        [0x11; 16],
+        "Árbol mañana dilema brecha árbol mañana dilema brecha árbol MAÑANA dilema brusco",
+      ),
+      (
+        Language::French,
+        [0x3c; 16],
+        "cubique sphère anxieux hangar psychose biscuit tronc jongler cubique SPHÈRE anxieux hasard",
+      ),
+    ];
+    for (language, entropy, typed) in cases {
+      assert!(typed.chars().any(|c| c as u32 > 0x7f));
+      assert_eq!(typed.nfc().collect::<String>(), typed);
+      let expected = Mnemonic::from_entropy_in(language, &entropy).unwrap();
+      assert_eq!(parse_mnemonic(typed, language).unwrap(), expected);
+
+      let decomposed: String = typed.nfkd().collect();
+      assert_eq!(parse_mnemonic(&decomposed, language).unwrap(), expected);
+    }
+  }
+
+  #[test]
+  fn suggests_accented_words() {
+    // Typed without the accent, the word is one mark away from the list
+    let arbol: String = "árbol".nfkd().collect();
+    assert_eq!(
+      suggest_word(Language::Spanish, "arbol"),
+      Some(arbol.as_str())
+    );
+    let sphere: String = "sphère".nfkd().collect();
+    assert_eq!(
+      suggest_word(Language::French, "spehre"),
+      Some(sphere.as_str())
+    );
+
+    match parse_mnemonic(
+      "arbol mañana dilema brecha árbol mañana dilema brecha árbol mañana dilema brusco",
+      Language::Spanish,
+    ) {
+      Err(MnemonicError::UnknownWord {
+        position: 1,
+        suggestion: Some(suggestion),
+        ..
+      }) => assert_eq!(suggestion, arbol),
+      other => panic!("unexpected result: {:?}", other.map(|m| m.to_string())),
+    }
+  }
+}
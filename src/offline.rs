@@ -0,0 +1,215 @@
+//! Network state checks behind the offline policy. Each finding names the
+//! interface or radio that makes the machine look online.
+
+/// What to do when the machine does not look offline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OfflinePolicy {
+  /// Refuse to run.
+  Require,
+  /// Report the findings and continue.
+  #[default]
+  Warn,
+  /// Skip the check.
+  Off,
+}
+
+impl OfflinePolicy {
+  pub const ALL: [OfflinePolicy; 3] = [
+    OfflinePolicy::Require,
+    OfflinePolicy::Warn,
+    OfflinePolicy::Off,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      OfflinePolicy::Require => "require",
+      OfflinePolicy::Warn => "warn",
+      OfflinePolicy::Off => "off",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|p| p.name() == name)
+  }
+}
+
+/// One reason the machine looks online.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+  /// Interface (`wlan0`) or radio (`phy0 (wlan)`) that tripped the check.
+  pub source: String,
+  pub reason: String,
+}
+
+/// Inspects the network state. An error means the state could not be read
+/// at all, which the caller should not take as "offline".
+pub fn check() -> anyhow::Result<Vec<Finding>> {
+  #[cfg(target_os = "linux")]
+  {
+    linux::check()
+  }
+  #[cfg(not(target_os = "linux"))]
+  {
+    addresses::check()
+  }
+}
+
+/// Linux: link state, default routes and radios from `/sys` and `/proc`.
+/// Virtual interfaces (bridges, `veth`, `ifb`, ...) only count when traffic
+/// is routed through them, and interfaces without a carrier are ignored.
+#[cfg(target_os = "linux")]
+mod linux {
+  use super::Finding;
+  use anyhow::Context;
+  use std::{fs, path::Path};
+
+  const SYS_NET: &str = "/sys/class/net";
+  const SYS_RFKILL: &str = "/sys/class/rfkill";
+  const SYS_VIRTUAL: &str = "/sys/devices/virtual";
+
+  const IFF_UP: u32 = 0x1;
+  const RTF_UP: u32 = 0x1;
+  const RTF_REJECT: u32 = 0x200;
+
+  /// Radio types that can bring the machine online.
+  const RADIOS: [&str; 3] = ["wlan", "bluetooth", "wwan"];
+
+  pub fn check() -> anyhow::Result<Vec<Finding>> {
+    let routes = default_routes();
+    let mut findings = Vec::new();
+
+    for name in entries(SYS_NET).context("Unable to list network interfaces")? {
+      if name == "lo" {
+        continue;
+      }
+      let dir = Path::new(SYS_NET).join(&name);
+      let admin_up = read(&dir.join("flags"))
+        .and_then(|flags| u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok())
+        .is_some_and(|flags| flags & IFF_UP != 0);
+      // Reading `carrier` fails while the interface is down
+      let carrier = read(&dir.join("carrier")).as_deref() == Some("1");
+      let is_virtual = fs::canonicalize(&dir).is_ok_and(|path| path.starts_with(SYS_VIRTUAL));
+      let wireless = dir.join("wireless").exists() || dir.join("phy80211").exists();
+
+      let mut reasons: Vec<String> = routes
+        .iter()
+        .filter(|(iface, _)| *iface == name)
+        .map(|(_, family)| format!("default {} route", family))
+        .collect();
+      if admin_up && carrier && !is_virtual {
+        reasons.push(if wireless {
+          "connected to a wireless network".to_string()
+        } else {
+          "link up (cable connected)".to_string()
+        });
+      } else if admin_up && wireless {
+        reasons.push("wireless interface enabled".to_string());
+      }
+
+      if !reasons.is_empty() {
+        findings.push(Finding {
+          source: name,
+          reason: reasons.join(", "),
+        });
+      }
+    }
+
+    // Missing on systems without radios
+    for name in entries(SYS_RFKILL).unwrap_or_default() {
+      let dir = Path::new(SYS_RFKILL).join(&name);
+      let kind = read(&dir.join("type")).unwrap_or_default();
+      let unblocked = read(&dir.join("soft")).as_deref() == Some("0")
+        && read(&dir.join("hard")).as_deref() == Some("0");
+      if RADIOS.contains(&kind.as_str()) && unblocked {
+        let device = read(&dir.join("name")).unwrap_or(name);
+        findings.push(Finding {
+          source: format!("{} ({})", device, kind),
+          reason: "radio not blocked by rfkill".to_string(),
+        });
+      }
+    }
+
+    Ok(findings)
+  }
+
+  /// Interfaces with an active, non-reject default route, and the address
+  /// family of the route.
+  fn default_routes() -> Vec<(String, &'static str)> {
+    let mut routes = Vec::new();
+
+    // Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+    for line in read("/proc/net/route".as_ref())
+      .unwrap_or_default()
+      .lines()
+      .skip(1)
+    {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      if let [iface, "00000000", _, flags, _, _, _, "00000000", ..] = fields[..]
+        && route_active(flags)
+      {
+        routes.push((iface.to_string(), "IPv4"));
+      }
+    }
+
+    // Destination PrefixLen Source SourceLen NextHop Metric RefCnt Use Flags Iface
+    for line in read("/proc/net/ipv6_route".as_ref())
+      .unwrap_or_default()
+      .lines()
+    {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      if let [destination, "00", _, _, _, _, _, _, flags, iface] = fields[..]
+        && destination.bytes().all(|b| b == b'0')
+        && iface != "lo"
+        && route_active(flags)
+      {
+        routes.push((iface.to_string(), "IPv6"));
+      }
+    }
+
+    routes
+  }
+
+  fn route_active(flags: &str) -> bool {
+    u32::from_str_radix(flags, 16).is_ok_and(|flags| flags & RTF_UP != 0 && flags & RTF_REJECT == 0)
+  }
+
+  fn entries(dir: &str) -> std::io::Result<Vec<String>> {
+    let mut names: Vec<String> = fs::read_dir(dir)?
+      .filter_map(|entry| entry.ok())
+      .map(|entry| entry.file_name().to_string_lossy().into_owned())
+      .collect();
+    names.sort();
+    Ok(names)
+  }
+
+  fn read(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+  }
+}
+
+/// Elsewhere: any interface with a routable address.
+#[cfg(not(target_os = "linux"))]
+mod addresses {
+  use super::Finding;
+  use if_addrs::IfAddr;
+
+  pub fn check() -> anyhow::Result<Vec<Finding>> {
+    Ok(
+      if_addrs::get_if_addrs()?
+        .into_iter()
+        .filter(|iface| !iface.is_loopback() && !is_link_local(&iface.addr))
+        .map(|iface| Finding {
+          reason: format!("address {}", iface.ip()),
+          source: iface.name,
+        })
+        .collect(),
+    )
+  }
+
+  fn is_link_local(addr: &IfAddr) -> bool {
+    match addr {
+      IfAddr::V4(v4) => v4.ip.is_link_local(),
+      IfAddr::V6(v6) => v6.ip.segments()[0] & 0xffc0 == 0xfe80,
+    }
+  }
+}
@@ -0,0 +1,180 @@
+//! Rough strength estimate for a BIP39 passphrase. It only looks at the
+//! character classes, predictable runs and word structure, so it can
+//! overrate a phrase that is well known or personal.
+
+/// Bits per word of a passphrase made of words, as if picked at random from
+/// a 7776-word diceware list.
+const BITS_PER_WORD: f64 = 12.925;
+/// Bits granted for a character that repeats or continues a sequence.
+const BITS_PER_PREDICTABLE: f64 = 1.0;
+/// Characters in each class of [`class`]: lowercase, uppercase, digits,
+/// other ASCII (symbols and space) and everything else.
+const CLASS_SIZES: [u32; 5] = [26, 26, 10, 33, 100];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rating {
+  VeryWeak,
+  Weak,
+  Fair,
+  Strong,
+  VeryStrong,
+}
+
+impl Rating {
+  fn from_bits(bits: f64) -> Self {
+    match bits {
+      b if b < 28.0 => Rating::VeryWeak,
+      b if b < 36.0 => Rating::Weak,
+      b if b < 60.0 => Rating::Fair,
+      b if b < 80.0 => Rating::Strong,
+      _ => Rating::VeryStrong,
+    }
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      Rating::VeryWeak => "very weak",
+      Rating::Weak => "weak",
+      Rating::Fair => "fair",
+      Rating::Strong => "strong",
+      Rating::VeryStrong => "very strong",
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Strength {
+  pub bits: f64,
+  pub rating: Rating,
+}
+
+/// Estimates how hard the passphrase is to guess. The lower of two models
+/// wins: random characters from the classes used, and random dictionary
+/// words when the passphrase looks like a phrase.
+pub fn estimate(passphrase: &str) -> Strength {
+  let bits = match word_count(passphrase) {
+    Some(words) => character_bits(passphrase).min(words as f64 * BITS_PER_WORD),
+    None => character_bits(passphrase),
+  };
+  Strength {
+    bits,
+    rating: Rating::from_bits(bits),
+  }
+}
+
+/// Characters drawn from the pool of the classes used; a character equal
+/// to, or next to, the previous one (`aaaa`, `1234`, `cba`) barely counts.
+fn character_bits(passphrase: &str) -> f64 {
+  let mut used = [false; CLASS_SIZES.len()];
+  for c in passphrase.chars() {
+    used[class(c)] = true;
+  }
+  let pool: u32 = CLASS_SIZES
+    .iter()
+    .zip(used)
+    .filter(|(_, used)| *used)
+    .map(|(size, _)| size)
+    .sum();
+  if pool == 0 {
+    return 0.0;
+  }
+
+  let mut bits = 0.0;
+  let mut previous: Option<char> = None;
+  for c in passphrase.chars() {
+    let predictable = previous.is_some_and(|p| (c as i64 - p as i64).abs() <= 1);
+    bits += if predictable {
+      BITS_PER_PREDICTABLE
+    } else {
+      f64::from(pool).log2()
+    };
+    previous = Some(c);
+  }
+  bits
+}
+
+fn class(c: char) -> usize {
+  match c {
+    'a'..='z' => 0,
+    'A'..='Z' => 1,
+    '0'..='9' => 2,
+    c if c.is_ascii() => 3,
+    _ => 4,
+  }
+}
+
+/// Number of words when the passphrase is two or more alphabetic words
+/// separated by spaces, hyphens, dots or underscores.
+fn word_count(passphrase: &str) -> Option<usize> {
+  let words: Vec<&str> = passphrase
+    .split([' ', '-', '.', '_'])
+    .filter(|w| !w.is_empty())
+    .collect();
+  let is_phrase = words.len() >= 2
+    && words
+      .iter()
+      .all(|w| w.chars().count() >= 2 && w.chars().all(char::is_alphabetic));
+  is_phrase.then_some(words.len())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rating(passphrase: &str) -> Rating {
+    estimate(passphrase).rating
+  }
+
+  #[test]
+  fn rating_thresholds() {
+    assert_eq!(Rating::from_bits(27.9), Rating::VeryWeak);
+    assert_eq!(Rating::from_bits(28.0), Rating::Weak);
+    assert_eq!(Rating::from_bits(36.0), Rating::Fair);
+    assert_eq!(Rating::from_bits(60.0), Rating::Strong);
+    assert_eq!(Rating::from_bits(80.0), Rating::VeryStrong);
+  }
+
+  #[test]
+  fn empty_passphrase_has_no_strength() {
+    assert_eq!(
+      estimate(""),
+      Strength {
+        bits: 0.0,
+        rating: Rating::VeryWeak
+      }
+    );
+  }
+
+  #[test]
+  fn short_words_are_weak() {
+    // 5 × log2(26) + 1 for the "on" step
+    let monkey = estimate("monkey");
+    assert!((monkey.bits - (5.0 * 26f64.log2() + 1.0)).abs() < 1e-9);
+    assert_eq!(monkey.rating, Rating::VeryWeak);
+    assert_eq!(rating("qzmxkwp"), Rating::Weak);
+  }
+
+  #[test]
+  fn predictable_runs_barely_count() {
+    assert_eq!(rating("aaaaaaaaaaaaaaaaaaaa"), Rating::VeryWeak);
+    assert_eq!(rating("12345678901234567890"), Rating::VeryWeak);
+  }
+
+  #[test]
+  fn phrases_count_words() {
+    assert_eq!(rating("correct horse"), Rating::VeryWeak);
+    assert_eq!(rating("correct horse battery staple"), Rating::Fair);
+    assert_eq!(rating("correct horse battery staple tiger"), Rating::Strong);
+    assert_eq!(
+      rating("correct-horse-battery-staple-tiger-lamp-ocean"),
+      Rating::VeryStrong
+    );
+  }
+
+  #[test]
+  fn long_mixed_phrase_is_very_strong() {
+    let strength = estimate("Tr0ub4dor&3-Xq!z9#Lm");
+    assert!(strength.bits > 100.0);
+    assert_eq!(strength.rating, Rating::VeryStrong);
+  }
+}
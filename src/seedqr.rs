@@ -0,0 +1,250 @@
+//! SeedQR and CompactSeedQR, the QR backup formats read by SeedSigner and
+//! Krux, rendered for the terminal and as SVG or PNG images.
+//!
+//! Both formats carry English wordlist indexes only, and are defined for 12
+//! and 24 words. A mnemonic in another language has a different BIP39 seed,
+//! so it cannot be stored as a SeedQR without changing the wallet.
+
+use anyhow::bail;
+use bip39::{Language, Mnemonic};
+use qrcode::{Color, EcLevel, QrCode, Version, bits::Bits};
+use std::fmt::Write;
+use zeroize::Zeroizing;
+
+/// Light modules around the code, as the QR specification requires.
+const QUIET_ZONE: usize = 4;
+/// Pixels per module in PNG images.
+const PNG_SCALE: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedQrFormat {
+  /// The 4-digit word indexes, one after another, in numeric mode.
+  Standard,
+  /// The entropy bytes without the checksum, in byte mode.
+  Compact,
+}
+
+impl SeedQrFormat {
+  pub const ALL: [SeedQrFormat; 2] = [SeedQrFormat::Standard, SeedQrFormat::Compact];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      SeedQrFormat::Standard => "standard",
+      SeedQrFormat::Compact => "compact",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|f| f.name() == name)
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      SeedQrFormat::Standard => "SeedQR (word indexes)",
+      SeedQrFormat::Compact => "CompactSeedQR (entropy bytes)",
+    }
+  }
+
+  /// QR version fixed by the SeedQR specification for each size.
+  fn version(self, word_count: usize) -> Version {
+    match (self, word_count) {
+      (SeedQrFormat::Standard, 12) => Version::Normal(2),
+      (SeedQrFormat::Standard, _) => Version::Normal(3),
+      (SeedQrFormat::Compact, 12) => Version::Normal(1),
+      (SeedQrFormat::Compact, _) => Version::Normal(2),
+    }
+  }
+}
+
+/// Fails when the mnemonic cannot be stored as a SeedQR.
+pub fn check_supported(mnemonic: &Mnemonic) -> anyhow::Result<()> {
+  if mnemonic.language() != Language::English {
+    bail!("SeedQR only stores English mnemonics");
+  }
+  let words = mnemonic.word_count();
+  if words != 12 && words != 24 {
+    bail!("SeedQR is defined for 12 and 24 words, not {}", words);
+  }
+  Ok(())
+}
+
+/// Encodes the mnemonic as a SeedQR code.
+pub fn encode(mnemonic: &Mnemonic, format: SeedQrFormat) -> anyhow::Result<QrCode> {
+  check_supported(mnemonic)?;
+
+  let version = format.version(mnemonic.word_count());
+  let mut bits = Bits::new(version);
+  match format {
+    SeedQrFormat::Standard => bits.push_numeric_data(standard_digits(mnemonic).as_bytes())?,
+    SeedQrFormat::Compact => {
+      let entropy = Zeroizing::new(mnemonic.to_entropy());
+      bits.push_byte_data(&entropy)?;
+    }
+  }
+  bits.push_terminator(EcLevel::L)?;
+
+  Ok(QrCode::with_bits(bits, EcLevel::L)?)
+}
+
+/// Payload of a standard SeedQR: each word index as four digits.
+fn standard_digits(mnemonic: &Mnemonic) -> Zeroizing<String> {
+  let mut digits = Zeroizing::new(String::with_capacity(mnemonic.word_count() * 4));
+  for index in mnemonic.word_indices() {
+    let _ = write!(digits, "{:04}", index);
+  }
+  digits
+}
+
+/// Modules of the code with the quiet zone, row by row; `true` is dark.
+pub(crate) fn modules(code: &QrCode) -> (usize, Zeroizing<Vec<bool>>) {
+  let width = code.width();
+  let size = width + 2 * QUIET_ZONE;
+  let colors = code.to_colors();
+  let mut modules = Zeroizing::new(vec![false; size * size]);
+  for y in 0..width {
+    for x in 0..width {
+      modules[(y + QUIET_ZONE) * size + x + QUIET_ZONE] = colors[y * width + x] == Color::Dark;
+    }
+  }
+  (size, modules)
+}
+
+/// Draws the code with Unicode half blocks, two module rows per line.
+/// Light modules are drawn as blocks, which reads correctly on the usual
+/// dark terminal background.
+pub fn render_terminal(code: &QrCode) -> Zeroizing<String> {
+  let (size, modules) = modules(code);
+  let light = |x: usize, y: usize| y >= size || !modules[y * size + x];
+
+  let mut out = Zeroizing::new(String::new());
+  for y in (0..size).step_by(2) {
+    for x in 0..size {
+      out.push(match (light(x, y), light(x, y + 1)) {
+        (true, true) => '█',
+        (true, false) => '▀',
+        (false, true) => '▄',
+        (false, false) => ' ',
+      });
+    }
+    out.push('\n');
+  }
+  out
+}
+
+/// SVG image of the code, one unit per module.
+pub fn render_svg(code: &QrCode) -> Zeroizing<String> {
+  let (size, modules) = modules(code);
+
+  let mut out = Zeroizing::new(String::new());
+  out.push_str(&format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {size} {size}\" \
+     width=\"{px}\" height=\"{px}\" shape-rendering=\"crispEdges\">\n\
+     <rect width=\"{size}\" height=\"{size}\" fill=\"#fff\"/>\n<path fill=\"#000\" d=\"",
+    size = size,
+    px = size * PNG_SCALE,
+  ));
+  for (i, _) in modules.iter().enumerate().filter(|(_, dark)| **dark) {
+    // Writing to a String cannot fail
+    let _ = write!(out, "M{} {}h1v1h-1z", i % size, i / size);
+  }
+  out.push_str("\"/>\n</svg>\n");
+  out
+}
+
+/// Grayscale PNG image of the code.
+pub fn render_png(code: &QrCode) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  let (size, modules) = modules(code);
+  let pixels = size * PNG_SCALE;
+
+  let mut image = Zeroizing::new(vec![0xffu8; pixels * pixels]);
+  for (i, _) in modules.iter().enumerate().filter(|(_, dark)| **dark) {
+    let (x, y) = (i % size * PNG_SCALE, i / size * PNG_SCALE);
+    for row in y..y + PNG_SCALE {
+      image[row * pixels + x..row * pixels + x + PNG_SCALE].fill(0);
+    }
+  }
+
+  let mut out = Zeroizing::new(Vec::new());
+  let mut encoder = png::Encoder::new(&mut *out, pixels as u32, pixels as u32);
+  encoder.set_color(png::ColorType::Grayscale);
+  encoder.set_depth(png::BitDepth::Eight);
+  let mut writer = encoder.write_header()?;
+  writer.write_image_data(&image)?;
+  writer.finish()?;
+  Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn mnemonic(phrase: &str) -> Mnemonic {
+    Mnemonic::parse_in(Language::English, phrase).unwrap()
+  }
+
+  // Examples from the SeedQR specification (SeedSigner)
+
+  #[test]
+  fn standard_digits_vectors() {
+    assert_eq!(
+      standard_digits(&mnemonic(
+        "forum undo fragile fade shy sign arrest garment culture tube off merit"
+      ))
+      .as_str(),
+      "073318950739065415961602009907670428187212261116"
+    );
+    assert_eq!(
+      standard_digits(&mnemonic(
+        "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday \
+         candy pony ranch winter theme error hybrid van cereal salon goddess expire"
+      ))
+      .as_str(),
+      "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643"
+    );
+    let abandon = mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    );
+    assert_eq!(
+      standard_digits(&abandon).as_str(),
+      format!("{}0003", "0000".repeat(11))
+    );
+  }
+
+  #[test]
+  fn versions_match_the_specification() {
+    let twelve = mnemonic("forum undo fragile fade shy sign arrest garment culture tube off merit");
+    let twenty_four = mnemonic(
+      "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy \
+       pony ranch winter theme error hybrid van cereal salon goddess expire",
+    );
+    let width = |m: &Mnemonic, format| encode(m, format).unwrap().width();
+    assert_eq!(width(&twelve, SeedQrFormat::Standard), 25);
+    assert_eq!(width(&twelve, SeedQrFormat::Compact), 21);
+    assert_eq!(width(&twenty_four, SeedQrFormat::Standard), 29);
+    assert_eq!(width(&twenty_four, SeedQrFormat::Compact), 25);
+  }
+
+  #[test]
+  fn standard_code_holds_the_digits() {
+    let m = mnemonic("forum undo fragile fade shy sign arrest garment culture tube off merit");
+    let expected = QrCode::with_version(
+      "073318950739065415961602009907670428187212261116",
+      Version::Normal(2),
+      EcLevel::L,
+    )
+    .unwrap();
+    assert_eq!(
+      encode(&m, SeedQrFormat::Standard).unwrap().to_colors(),
+      expected.to_colors()
+    );
+  }
+
+  #[test]
+  fn rejects_unsupported_mnemonics() {
+    let fifteen = Mnemonic::from_entropy(&[0; 20]).unwrap();
+    assert!(check_supported(&fifteen).is_err());
+    let spanish = Mnemonic::from_entropy_in(Language::Spanish, &[0; 16]).unwrap();
+    assert!(check_supported(&spanish).is_err());
+    assert!(encode(&spanish, SeedQrFormat::Compact).is_err());
+  }
+}
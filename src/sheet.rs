@@ -0,0 +1,646 @@
+//! Printable A4 backup sheet, written as SVG or PDF without any external
+//! tool: the page is laid out once as text, boxes and filled rectangles,
+//! then serialized to either format.
+
+use anyhow::bail;
+use qrcode::{EcLevel, QrCode};
+use std::fmt::Write;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::{
+  meta, mnemonic, seedqr,
+  wallet::{self, DerivedWallet},
+};
+
+/// A4 in PostScript points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 40.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+const WORD_COLUMNS: usize = 3;
+const WORD_ROW_HEIGHT: f32 = 30.0;
+const QR_SIZE: f32 = 170.0;
+
+/// What the sheet shows of the seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SheetMode {
+  /// Words and indexes, with the public wallet details.
+  Full,
+  /// Empty numbered boxes to write the words in by hand, so the seed never
+  /// reaches the printer.
+  Template,
+  /// No seed at all: public key and descriptors for a watch-only setup.
+  Public,
+}
+
+impl SheetMode {
+  pub const ALL: [SheetMode; 3] = [SheetMode::Full, SheetMode::Template, SheetMode::Public];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      SheetMode::Full => "full",
+      SheetMode::Template => "template",
+      SheetMode::Public => "public",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|m| m.name() == name)
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      SheetMode::Full => "Full (words + indexes + public details)",
+      SheetMode::Template => "Template (blank word boxes to fill in by hand)",
+      SheetMode::Public => "Public only (xpub + descriptor QR, watch-only)",
+    }
+  }
+
+  fn notice(self) -> &'static str {
+    match self {
+      SheetMode::Full => {
+        "SEED BACKUP — anyone holding this sheet controls the funds. Never photograph it."
+      }
+      SheetMode::Template => {
+        "SEED BACKUP TEMPLATE — once filled in, this sheet controls the funds."
+      }
+      SheetMode::Public => "WATCH-ONLY — public keys only; this sheet cannot spend funds.",
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Font {
+  Regular,
+  Bold,
+  Mono,
+  MonoBold,
+}
+
+impl Font {
+  /// Resource name in the PDF page.
+  fn pdf_name(self) -> &'static str {
+    match self {
+      Font::Regular => "F1",
+      Font::Bold => "F2",
+      Font::Mono => "F3",
+      Font::MonoBold => "F4",
+    }
+  }
+}
+
+/// Positions are in points from the top-left corner; text `y` is the
+/// baseline.
+enum Item {
+  Text {
+    x: f32,
+    y: f32,
+    size: f32,
+    font: Font,
+    text: Zeroizing<String>,
+  },
+  Frame {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+  },
+  Fill {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+  },
+}
+
+#[derive(Default)]
+struct Page {
+  items: Vec<Item>,
+}
+
+impl Page {
+  fn text(&mut self, x: f32, y: f32, size: f32, font: Font, text: impl Into<String>) {
+    self.items.push(Item::Text {
+      x,
+      y,
+      size,
+      font,
+      text: Zeroizing::new(text.into()),
+    });
+  }
+
+  /// Monospaced text broken into lines that fit `width`; returns the
+  /// baseline after the last line.
+  fn wrapped(&mut self, x: f32, mut y: f32, width: f32, size: f32, text: &str) -> f32 {
+    // Courier glyphs are 0.6 em wide
+    let per_line = ((width / (size * 0.6)) as usize).max(1);
+    let chars: Vec<char> = text.chars().collect();
+    for line in chars.chunks(per_line) {
+      self.text(x, y, size, Font::Mono, line.iter().collect::<String>());
+      y += size * 1.3;
+    }
+    y
+  }
+
+  /// QR code of `data` in a `size` square, dark modules merged into runs.
+  fn qr(&mut self, x: f32, y: f32, size: f32, data: &str) -> anyhow::Result<()> {
+    let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::M)?;
+    let (modules_per_side, modules) = seedqr::modules(&code);
+    let module = size / modules_per_side as f32;
+
+    for (row, line) in modules.chunks(modules_per_side).enumerate() {
+      let mut col = 0;
+      while col < modules_per_side {
+        if !line[col] {
+          col += 1;
+          continue;
+        }
+        let start = col;
+        while col < modules_per_side && line[col] {
+          col += 1;
+        }
+        self.items.push(Item::Fill {
+          x: x + start as f32 * module,
+          y: y + row as f32 * module,
+          w: (col - start) as f32 * module,
+          h: module,
+        });
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Backup sheet of one account as an SVG document.
+pub fn render_svg(
+  wallet: &DerivedWallet,
+  mode: SheetMode,
+  passphrase_used: bool,
+) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  let page = layout(wallet, mode, passphrase_used)?;
+  Ok(Zeroizing::new(to_svg(&page).as_bytes().to_vec()))
+}
+
+/// Backup sheet of one account as a single-page PDF document.
+pub fn render_pdf(
+  wallet: &DerivedWallet,
+  mode: SheetMode,
+  passphrase_used: bool,
+) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  to_pdf(&layout(wallet, mode, passphrase_used)?)
+}
+
+fn layout(wallet: &DerivedWallet, mode: SheetMode, passphrase_used: bool) -> anyhow::Result<Page> {
+  let mut page = Page::default();
+  // A public sheet says nothing about the seed behind the keys
+  let mnemonic = match (mode, &wallet.mnemonic) {
+    (SheetMode::Public, _) => None,
+    (_, Some(mnemonic)) => Some(mnemonic),
+    (_, None) => bail!(
+      "The wallet has no mnemonic (it comes from SLIP-39 shares); only a public sheet can be made"
+    ),
+  };
+
+  page.text(
+    MARGIN,
+    MARGIN + 16.0,
+    20.0,
+    Font::Bold,
+    format!("{} backup sheet", meta::PROJECT_NAME),
+  );
+  page.text(MARGIN, MARGIN + 36.0, 10.0, Font::Regular, mode.notice());
+  page.items.push(Item::Fill {
+    x: MARGIN,
+    y: MARGIN + 44.0,
+    w: CONTENT_WIDTH,
+    h: 0.8,
+  });
+
+  // WALLET DETAILS
+  let passphrase = if passphrase_used {
+    "Yes — not printed, keep it separately"
+  } else {
+    "None"
+  };
+  let mut details = vec![
+    ("Fingerprint", wallet.fingerprint.to_string()),
+    ("Network", wallet::network_name(wallet.network).to_string()),
+    (
+      "Script type",
+      format!(
+        "{} ({})",
+        wallet.script_type.name().to_uppercase(),
+        wallet.script_type.output_name()
+      ),
+    ),
+    ("Derivation path", wallet.derivation_path_string()),
+  ];
+  if let Some(mnemonic) = mnemonic {
+    details.push((
+      "Mnemonic",
+      format!(
+        "{} words, {}",
+        mnemonic.word_count(),
+        mnemonic::language_label(mnemonic.language())
+      ),
+    ));
+    details.push(("Passphrase", passphrase.to_string()));
+  }
+  let mut y = MARGIN + 66.0;
+  for (label, value) in details {
+    page.text(MARGIN, y, 10.0, Font::Bold, label);
+    page.text(MARGIN + 110.0, y, 10.0, Font::Mono, value);
+    y += 16.0;
+  }
+
+  // WORDS
+  if let Some(mnemonic) = mnemonic {
+    y += 12.0;
+    let title = match mode {
+      SheetMode::Template => "Mnemonic words (write each word and its index)",
+      _ => "Mnemonic words",
+    };
+    page.text(MARGIN, y, 12.0, Font::Bold, title);
+    y += 10.0;
+
+    let column = CONTENT_WIDTH / WORD_COLUMNS as f32;
+    let words = mnemonic.words().zip(mnemonic.word_indices());
+    for (i, (word, index)) in words.enumerate() {
+      let x = MARGIN + (i % WORD_COLUMNS) as f32 * column;
+      let top = y + (i / WORD_COLUMNS) as f32 * WORD_ROW_HEIGHT;
+      page.items.push(Item::Frame {
+        x: x + 2.0,
+        y: top,
+        w: column - 4.0,
+        h: WORD_ROW_HEIGHT - 4.0,
+      });
+      page.text(
+        x + 8.0,
+        top + 16.0,
+        8.0,
+        Font::Regular,
+        format!("{:02}", i + 1),
+      );
+      match mode {
+        SheetMode::Template => page.items.push(Item::Frame {
+          x: x + column - 48.0,
+          y: top + 5.0,
+          w: 40.0,
+          h: 16.0,
+        }),
+        _ => {
+          page.text(x + 26.0, top + 17.0, 12.0, Font::MonoBold, word);
+          page.text(
+            x + column - 34.0,
+            top + 17.0,
+            8.0,
+            Font::Mono,
+            format!("{:04}", index + 1),
+          );
+        }
+      }
+    }
+    y += mnemonic.word_count().div_ceil(WORD_COLUMNS) as f32 * WORD_ROW_HEIGHT;
+  }
+
+  // PUBLIC KEY
+  y += 14.0;
+  page.text(MARGIN, y, 12.0, Font::Bold, "Account public key");
+  y = page.wrapped(MARGIN, y + 14.0, CONTENT_WIDTH, 7.5, &wallet.slip132_xpub());
+
+  // DESCRIPTORS
+  y += 8.0;
+  let column = CONTENT_WIDTH / 2.0;
+  let descriptors = [
+    ("Receive descriptor", &wallet.descriptor_receive),
+    ("Change descriptor", &wallet.descriptor_change),
+  ];
+  let mut bottom = y;
+  for (i, (label, descriptor)) in descriptors.into_iter().enumerate() {
+    let x = MARGIN + i as f32 * column;
+    page.text(x, y + 10.0, 10.0, Font::Bold, label);
+    page.qr(x, y + 14.0, QR_SIZE, descriptor)?;
+    bottom = bottom.max(page.wrapped(x, y + QR_SIZE + 22.0, column - 12.0, 6.0, descriptor));
+  }
+
+  if bottom > PAGE_HEIGHT - MARGIN {
+    bail!("The backup sheet does not fit on one page");
+  }
+
+  page.text(
+    MARGIN,
+    PAGE_HEIGHT - MARGIN + 16.0,
+    7.0,
+    Font::Regular,
+    format!(
+      "Generated offline by {} {} — {}",
+      meta::PROJECT_NAME,
+      meta::VERSION,
+      meta::PROJECT_REPOSITORY
+    ),
+  );
+
+  Ok(page)
+}
+
+fn to_svg(page: &Page) -> Zeroizing<String> {
+  let mut out = Zeroizing::new(String::new());
+  // Writing to a String cannot fail
+  let _ = write!(
+    out,
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"210mm\" height=\"297mm\" \
+     viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"#fff\"/>\n",
+    w = PAGE_WIDTH,
+    h = PAGE_HEIGHT,
+  );
+
+  for item in &page.items {
+    let _ = match item {
+      Item::Text {
+        x,
+        y,
+        size,
+        font,
+        text,
+      } => {
+        let (family, weight) = match font {
+          Font::Regular => ("Helvetica, Arial, sans-serif", "normal"),
+          Font::Bold => ("Helvetica, Arial, sans-serif", "bold"),
+          Font::Mono => ("Courier, 'Courier New', monospace", "normal"),
+          Font::MonoBold => ("Courier, 'Courier New', monospace", "bold"),
+        };
+        let mut escaped = Zeroizing::new(String::with_capacity(text.len()));
+        for c in text.chars() {
+          match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+          }
+        }
+        writeln!(
+          out,
+          "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{}\" font-family=\"{}\" font-weight=\"{}\">{}</text>",
+          x,
+          y,
+          size,
+          family,
+          weight,
+          escaped.as_str()
+        )
+      }
+      Item::Frame { x, y, w, h } => writeln!(
+        out,
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.5\"/>",
+        x, y, w, h
+      ),
+      Item::Fill { x, y, w, h } => writeln!(
+        out,
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"#000\"/>",
+        x, y, w, h
+      ),
+    };
+  }
+
+  out.push_str("</svg>\n");
+  out
+}
+
+fn to_pdf(page: &Page) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  // PDF coordinates start at the bottom-left corner
+  let mut content = Zeroizing::new(Vec::new());
+  for item in &page.items {
+    match item {
+      Item::Text {
+        x,
+        y,
+        size,
+        font,
+        text,
+      } => {
+        content.extend_from_slice(
+          format!(
+            "BT /{} {} Tf {:.2} {:.2} Td (",
+            font.pdf_name(),
+            size,
+            x,
+            PAGE_HEIGHT - y
+          )
+          .as_bytes(),
+        );
+        for byte in win_ansi(text)?.iter() {
+          match byte {
+            b'(' | b')' | b'\\' => content.extend_from_slice(&[b'\\', *byte]),
+            0x20..=0x7e => content.push(*byte),
+            _ => content.extend_from_slice(format!("\\{:03o}", byte).as_bytes()),
+          }
+        }
+        content.extend_from_slice(b") Tj ET\n");
+      }
+      Item::Frame { x, y, w, h } => content.extend_from_slice(
+        format!(
+          "0.5 w {:.2} {:.2} {:.2} {:.2} re S\n",
+          x,
+          PAGE_HEIGHT - y - h,
+          w,
+          h
+        )
+        .as_bytes(),
+      ),
+      Item::Fill { x, y, w, h } => content.extend_from_slice(
+        format!(
+          "{:.2} {:.2} {:.2} {:.2} re f\n",
+          x,
+          PAGE_HEIGHT - y - h,
+          w,
+          h
+        )
+        .as_bytes(),
+      ),
+    }
+  }
+
+  let fonts = ["Helvetica", "Helvetica-Bold", "Courier", "Courier-Bold"];
+  let mut objects: Vec<Vec<u8>> = vec![
+    b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+    b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+    format!(
+      "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+       /Resources << /Font << /F1 5 0 R /F2 6 0 R /F3 7 0 R /F4 8 0 R >> >> \
+       /Contents 4 0 R >>",
+      PAGE_WIDTH, PAGE_HEIGHT
+    )
+    .into_bytes(),
+  ];
+  let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+  stream.extend_from_slice(&content);
+  stream.extend_from_slice(b"\nendstream");
+  objects.push(stream);
+  for font in fonts {
+    objects.push(
+      format!(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+        font
+      )
+      .into_bytes(),
+    );
+  }
+
+  let mut out = Zeroizing::new(b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec());
+  let mut offsets = Vec::with_capacity(objects.len());
+  for (i, object) in objects.iter_mut().enumerate() {
+    offsets.push(out.len());
+    out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+    out.extend_from_slice(object);
+    out.extend_from_slice(b"\nendobj\n");
+    // The content stream holds the words
+    zeroize::Zeroize::zeroize(object);
+  }
+
+  let xref = out.len();
+  out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).as_bytes());
+  for offset in offsets {
+    out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+  }
+  out.extend_from_slice(
+    format!(
+      "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+      objects.len() + 1,
+      xref
+    )
+    .as_bytes(),
+  );
+  Ok(out)
+}
+
+/// Text in the WinAnsi encoding of the standard PDF fonts. Wordlists are
+/// stored decomposed (NFKD), so accents are composed back first.
+fn win_ansi(text: &str) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  let mut bytes = Zeroizing::new(Vec::with_capacity(text.len()));
+  for c in text.nfc() {
+    bytes.push(match c {
+      ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+      '—' => 0x97,
+      '–' => 0x96,
+      '…' => 0x85,
+      _ => bail!(
+        "The PDF sheet cannot show '{}'; save the sheet as SVG for this wordlist",
+        c
+      ),
+    });
+  }
+  Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::wallet::{AddressRange, ScriptType, WalletParams, WalletSeed};
+  use bip39::Mnemonic;
+  use bitcoin::Network;
+
+  fn derive(word_count: usize) -> DerivedWallet {
+    let entropy = vec![0x5a; word_count / 3 * 4];
+    wallet::derive_wallet(WalletParams {
+      seed: WalletSeed::Mnemonic {
+        mnemonic: Mnemonic::from_entropy(&entropy).unwrap(),
+        passphrase: Zeroizing::new(String::new()),
+      },
+      network: Network::Bitcoin,
+      script_type: ScriptType::Bip84,
+      account: 0,
+      addresses: AddressRange::default(),
+      dice: None,
+    })
+    .unwrap()
+  }
+
+  fn svg(wallet: &DerivedWallet, mode: SheetMode) -> String {
+    String::from_utf8(render_svg(wallet, mode, false).unwrap().to_vec()).unwrap()
+  }
+
+  /// Words and indexes as they appear in the SVG text elements.
+  fn seed_texts(wallet: &DerivedWallet) -> (Vec<String>, Vec<String>) {
+    let mnemonic = wallet.mnemonic.as_ref().unwrap();
+    let words = mnemonic.words().map(|w| format!(">{}</text>", w)).collect();
+    let indexes = mnemonic
+      .word_indices()
+      .map(|i| format!(">{:04}</text>", i + 1))
+      .collect();
+    (words, indexes)
+  }
+
+  #[test]
+  fn full_sheet_shows_words_and_indexes() {
+    let wallet = derive(12);
+    let out = svg(&wallet, SheetMode::Full);
+    let (words, indexes) = seed_texts(&wallet);
+    assert!(words.iter().all(|w| out.contains(w.as_str())));
+    assert!(indexes.iter().all(|i| out.contains(i.as_str())));
+  }
+
+  #[test]
+  fn public_sheet_has_no_seed() {
+    let wallet = derive(24);
+    let out = svg(&wallet, SheetMode::Public);
+    let (words, indexes) = seed_texts(&wallet);
+    assert!(words.iter().all(|w| !out.contains(w.as_str())));
+    assert!(indexes.iter().all(|i| !out.contains(i.as_str())));
+    assert!(!out.contains("Mnemonic"));
+    assert!(out.contains(&wallet.slip132_xpub()[..40]));
+  }
+
+  #[test]
+  fn template_sheet_has_no_words() {
+    let wallet = derive(24);
+    let out = svg(&wallet, SheetMode::Template);
+    let (words, indexes) = seed_texts(&wallet);
+    assert!(words.iter().all(|w| !out.contains(w.as_str())));
+    assert!(indexes.iter().all(|i| !out.contains(i.as_str())));
+  }
+
+  #[test]
+  fn full_24_word_sheet_fits_one_page() {
+    let wallet = derive(24);
+    for mode in SheetMode::ALL {
+      layout(&wallet, mode, true).unwrap();
+    }
+  }
+
+  #[test]
+  fn pdf_is_well_formed() {
+    let pdf = render_pdf(&derive(24), SheetMode::Full, false).unwrap();
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+
+    // startxref points at the cross-reference table
+    let text = String::from_utf8_lossy(&pdf);
+    let offset: usize = text
+      .rsplit("startxref\n")
+      .next()
+      .and_then(|tail| tail.lines().next())
+      .unwrap()
+      .parse()
+      .unwrap();
+    assert!(pdf[offset..].starts_with(b"xref\n"));
+  }
+
+  #[test]
+  fn svg_has_one_root_element() {
+    let out = svg(&derive(24), SheetMode::Full);
+    assert!(out.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(out.ends_with("</svg>\n"));
+    assert_eq!(out.matches("<svg").count(), 1);
+    assert_eq!(out.matches("<text").count(), out.matches("</text>").count());
+  }
+
+  #[test]
+  fn sheet_without_mnemonic_is_public_only() {
+    let mut wallet = derive(12);
+    wallet.mnemonic = None;
+    assert!(render_svg(&wallet, SheetMode::Full, false).is_err());
+    assert!(render_svg(&wallet, SheetMode::Public, false).is_ok());
+  }
+}
@@ -0,0 +1,763 @@
+//! SLIP-39 Shamir backup: splits a master secret into share mnemonics and
+//! combines them back.
+//!
+//! The master secret is the BIP32 seed, as SLIP-39 specifies, so shares from
+//! any SLIP-39 wallet recover the same wallet. A mnemonic wallet is shared
+//! through its 512-bit BIP39 seed, which never gives the mnemonic back.
+
+use anyhow::{Context, bail};
+use hmac::{Hmac, Mac};
+use rand::RngExt;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+const WORDLIST_TXT: &str = include_str!("slip39/wordlist.txt");
+
+const RADIX_BITS: usize = 10;
+const ID_BITS: usize = 15;
+const CHECKSUM_WORDS: usize = 3;
+/// Identifier, extendable flag, iteration exponent, group and member fields.
+const METADATA_BITS: usize = 40;
+const MIN_WORDS: usize = 20;
+const MIN_SECRET_BYTES: usize = 16;
+const MAX_SHARES: u8 = 16;
+
+const DIGEST_BYTES: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+
+const BASE_ITERATION_COUNT: u32 = 10_000;
+const ROUND_COUNT: u8 = 4;
+/// PBKDF2 work factor of new shares: 10000 << 1 iterations, as Trezor uses.
+const ITERATION_EXPONENT: u8 = 1;
+
+const CUSTOMIZATION: &str = "shamir";
+const CUSTOMIZATION_EXTENDABLE: &str = "shamir_extendable";
+
+/// Members of one group: any `threshold` of its `count` shares rebuild the
+/// group secret.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupSpec {
+  pub threshold: u8,
+  pub count: u8,
+}
+
+/// Share layout: any `group_threshold` groups are needed to recover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharingSpec {
+  pub group_threshold: u8,
+  pub groups: Vec<GroupSpec>,
+}
+
+impl SharingSpec {
+  /// Parses `2of3` (one group) or `2:2of3,3of5,1of1` (group threshold
+  /// before the colon, then each group's member threshold and count).
+  pub fn parse(value: &str) -> anyhow::Result<Self> {
+    let (group_threshold, groups) = match value.split_once(':') {
+      Some((threshold, groups)) => (
+        threshold
+          .trim()
+          .parse::<u8>()
+          .with_context(|| format!("Invalid group threshold '{}'", threshold.trim()))?,
+        groups,
+      ),
+      None => (1, value),
+    };
+
+    let groups = groups
+      .split(',')
+      .map(|group| {
+        let group = group.trim();
+        let parsed = group
+          .split_once("of")
+          .and_then(|(t, n)| Some((t.trim().parse().ok()?, n.trim().parse().ok()?)));
+        match parsed {
+          Some((threshold, count)) => Ok(GroupSpec { threshold, count }),
+          None => bail!("Invalid group '{}': expected e.g. 2of3", group),
+        }
+      })
+      .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let spec = SharingSpec {
+      group_threshold,
+      groups,
+    };
+    spec.validate()?;
+    Ok(spec)
+  }
+
+  fn validate(&self) -> anyhow::Result<()> {
+    let group_count = self.groups.len();
+    if group_count == 0 || group_count > usize::from(MAX_SHARES) {
+      bail!("Between 1 and {} groups are allowed", MAX_SHARES);
+    }
+    if self.group_threshold == 0 || usize::from(self.group_threshold) > group_count {
+      bail!(
+        "Group threshold {} must be between 1 and the number of groups ({})",
+        self.group_threshold,
+        group_count
+      );
+    }
+    for group in &self.groups {
+      if group.count == 0 || group.count > MAX_SHARES {
+        bail!("A group holds between 1 and {} shares", MAX_SHARES);
+      }
+      if group.threshold == 0 || group.threshold > group.count {
+        bail!(
+          "Member threshold {} must be between 1 and the group size ({})",
+          group.threshold,
+          group.count
+        );
+      }
+      if group.threshold == 1 && group.count > 1 {
+        bail!(
+          "A 1of{} group is not allowed by SLIP-39, use 1of1",
+          group.count
+        );
+      }
+    }
+    Ok(())
+  }
+
+  pub fn share_count(&self) -> usize {
+    self.groups.iter().map(|g| usize::from(g.count)).sum()
+  }
+}
+
+impl std::fmt::Display for SharingSpec {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    let groups: Vec<String> = self
+      .groups
+      .iter()
+      .map(|g| format!("{}of{}", g.threshold, g.count))
+      .collect();
+    if self.groups.len() == 1 {
+      write!(f, "{}", groups[0])
+    } else {
+      write!(f, "{}:{}", self.group_threshold, groups.join(","))
+    }
+  }
+}
+
+/// A decoded share mnemonic.
+#[derive(Clone)]
+pub struct Share {
+  pub identifier: u16,
+  pub extendable: bool,
+  pub iteration_exponent: u8,
+  pub group_index: u8,
+  pub group_threshold: u8,
+  pub group_count: u8,
+  pub member_index: u8,
+  pub member_threshold: u8,
+  pub value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+  /// Decodes and checks a share mnemonic: wordlist, length, checksum and
+  /// padding.
+  pub fn parse(mnemonic: &str) -> anyhow::Result<Self> {
+    let words = Zeroizing::new(
+      mnemonic
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| match word_index(&word.to_lowercase()) {
+          Some(index) => Ok(index),
+          None => bail!(
+            "Word {:02} '{}' is not in the SLIP-39 wordlist",
+            i + 1,
+            word
+          ),
+        })
+        .collect::<anyhow::Result<Vec<u16>>>()?,
+    );
+
+    if words.len() < MIN_WORDS {
+      bail!(
+        "A SLIP-39 share has at least {} words, got {}",
+        MIN_WORDS,
+        words.len()
+      );
+    }
+
+    let padding = (RADIX_BITS * (words.len() - METADATA_BITS / RADIX_BITS - CHECKSUM_WORDS)) % 16;
+    if padding > 8 {
+      bail!("Invalid share length: {} words", words.len());
+    }
+
+    let bits = Zeroizing::new(unpack_bits(&words[..words.len() - CHECKSUM_WORDS]));
+    let field = |start: usize, len: usize| read_bits(&bits, start, len);
+
+    let extendable = field(ID_BITS, 1) == 1;
+    if rs1024_polymod(customization(extendable), &words) != 1 {
+      bail!("Invalid share checksum: a word is wrong or out of order");
+    }
+
+    if field(METADATA_BITS, padding) != 0 {
+      bail!("Invalid share padding");
+    }
+
+    let value = Zeroizing::new(
+      bits[METADATA_BITS + padding..]
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| acc << 1 | bit))
+        .collect::<Vec<u8>>(),
+    );
+
+    let share = Share {
+      identifier: field(0, ID_BITS) as u16,
+      extendable,
+      iteration_exponent: field(16, 4) as u8,
+      group_index: field(20, 4) as u8,
+      group_threshold: field(24, 4) as u8 + 1,
+      group_count: field(28, 4) as u8 + 1,
+      member_index: field(32, 4) as u8,
+      member_threshold: field(36, 4) as u8 + 1,
+      value,
+    };
+
+    if share.group_threshold > share.group_count {
+      bail!("Invalid share: group threshold exceeds group count");
+    }
+
+    Ok(share)
+  }
+
+  fn to_mnemonic(&self) -> Zeroizing<String> {
+    let mut bits = Zeroizing::new(Vec::with_capacity(
+      METADATA_BITS + RADIX_BITS + self.value.len() * 8,
+    ));
+    push_bits(&mut bits, u32::from(self.identifier), ID_BITS);
+    push_bits(&mut bits, u32::from(self.extendable), 1);
+    push_bits(&mut bits, u32::from(self.iteration_exponent), 4);
+    push_bits(&mut bits, u32::from(self.group_index), 4);
+    push_bits(&mut bits, u32::from(self.group_threshold - 1), 4);
+    push_bits(&mut bits, u32::from(self.group_count - 1), 4);
+    push_bits(&mut bits, u32::from(self.member_index), 4);
+    push_bits(&mut bits, u32::from(self.member_threshold - 1), 4);
+    let padding = (RADIX_BITS - self.value.len() * 8 % RADIX_BITS) % RADIX_BITS;
+    push_bits(&mut bits, 0, padding);
+    for &byte in self.value.iter() {
+      push_bits(&mut bits, u32::from(byte), 8);
+    }
+
+    let mut words = Zeroizing::new(
+      bits
+        .chunks(RADIX_BITS)
+        .map(|chunk| {
+          chunk
+            .iter()
+            .fold(0u16, |acc, bit| acc << 1 | u16::from(*bit))
+        })
+        .collect::<Vec<u16>>(),
+    );
+    let checksum = rs1024_checksum(customization(self.extendable), &words);
+    words.extend(checksum);
+
+    let list = wordlist();
+    Zeroizing::new(
+      words
+        .iter()
+        .map(|&w| list[usize::from(w)])
+        .collect::<Vec<_>>()
+        .join(" "),
+    )
+  }
+
+  /// True when both shares come from the same split.
+  pub fn same_set(&self, other: &Share) -> bool {
+    self.identifier == other.identifier
+      && self.extendable == other.extendable
+      && self.iteration_exponent == other.iteration_exponent
+      && self.group_threshold == other.group_threshold
+      && self.group_count == other.group_count
+      && self.value.len() == other.value.len()
+  }
+}
+
+/// Share mnemonics of one group, in member order.
+pub struct ShareGroup {
+  pub threshold: u8,
+  pub shares: Vec<Zeroizing<String>>,
+}
+
+/// Splits `secret` into share mnemonics laid out as `spec`. The secret is
+/// encrypted with `passphrase` first, as SLIP-39 requires.
+pub fn split(
+  secret: &[u8],
+  spec: &SharingSpec,
+  passphrase: &[u8],
+) -> anyhow::Result<Vec<ShareGroup>> {
+  spec.validate()?;
+  if secret.len() < MIN_SECRET_BYTES || !secret.len().is_multiple_of(2) {
+    bail!(
+      "The secret must be an even number of bytes, at least {}",
+      MIN_SECRET_BYTES
+    );
+  }
+
+  let mut rng = rand::rng();
+  let identifier: u16 = rng.random_range(0..1 << ID_BITS);
+  let extendable = true;
+  let encrypted = encrypt(
+    secret,
+    passphrase,
+    ITERATION_EXPONENT,
+    identifier,
+    extendable,
+  );
+  let group_count = spec.groups.len() as u8;
+
+  split_secret(spec.group_threshold, group_count, &encrypted)
+    .into_iter()
+    .zip(&spec.groups)
+    .map(|((group_index, group_secret), group)| {
+      let shares = split_secret(group.threshold, group.count, &group_secret)
+        .into_iter()
+        .map(|(member_index, value)| {
+          Share {
+            identifier,
+            extendable,
+            iteration_exponent: ITERATION_EXPONENT,
+            group_index,
+            group_threshold: spec.group_threshold,
+            group_count,
+            member_index,
+            member_threshold: group.threshold,
+            value,
+          }
+          .to_mnemonic()
+        })
+        .collect();
+      Ok(ShareGroup {
+        threshold: group.threshold,
+        shares,
+      })
+    })
+    .collect()
+}
+
+/// Progress of a recovery: groups with enough members versus the groups
+/// required.
+pub fn recovery_progress(shares: &[Share]) -> (usize, usize) {
+  let Some(first) = shares.first() else {
+    return (0, 1);
+  };
+  let complete = group_members(shares)
+    .values()
+    .filter(|members| members.len() >= usize::from(members[0].member_threshold))
+    .count();
+  (complete, usize::from(first.group_threshold))
+}
+
+/// Rebuilds the secret from decoded shares. Extra shares are ignored.
+pub fn combine(shares: &[Share], passphrase: &[u8]) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  let Some(first) = shares.first() else {
+    bail!("No shares given");
+  };
+  if shares.iter().any(|s| !first.same_set(s)) {
+    bail!("The shares do not belong to the same backup");
+  }
+
+  let groups = group_members(shares);
+  let mut group_secrets: Vec<(u8, Zeroizing<Vec<u8>>)> = Vec::new();
+  for (group_index, members) in &groups {
+    let threshold = members[0].member_threshold;
+    if members.iter().any(|m| m.member_threshold != threshold) {
+      bail!("Group {} shares disagree on the threshold", group_index + 1);
+    }
+    if members.len() < usize::from(threshold) {
+      continue;
+    }
+    let points: Vec<(u8, &[u8])> = members
+      .iter()
+      .take(usize::from(threshold))
+      .map(|m| (m.member_index, &m.value[..]))
+      .collect();
+    group_secrets.push((*group_index, recover_secret(threshold, &points)?));
+  }
+
+  if group_secrets.len() < usize::from(first.group_threshold) {
+    bail!(
+      "Not enough shares: {} of {} required groups are complete",
+      group_secrets.len(),
+      first.group_threshold
+    );
+  }
+
+  let points: Vec<(u8, &[u8])> = group_secrets
+    .iter()
+    .take(usize::from(first.group_threshold))
+    .map(|(index, secret)| (*index, &secret[..]))
+    .collect();
+  let encrypted = recover_secret(first.group_threshold, &points)?;
+
+  Ok(decrypt(
+    &encrypted,
+    passphrase,
+    first.iteration_exponent,
+    first.identifier,
+    first.extendable,
+  ))
+}
+
+/// Distinct members per group; a share typed twice counts once.
+fn group_members(shares: &[Share]) -> BTreeMap<u8, Vec<&Share>> {
+  let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+  for share in shares {
+    let members = groups.entry(share.group_index).or_default();
+    if !members.iter().any(|m| m.member_index == share.member_index) {
+      members.push(share);
+    }
+  }
+  groups
+}
+
+pub fn wordlist() -> Vec<&'static str> {
+  WORDLIST_TXT.lines().collect()
+}
+
+fn word_index(word: &str) -> Option<u16> {
+  WORDLIST_TXT
+    .lines()
+    .position(|w| w == word)
+    .map(|i| i as u16)
+}
+
+fn customization(extendable: bool) -> &'static str {
+  if extendable {
+    CUSTOMIZATION_EXTENDABLE
+  } else {
+    CUSTOMIZATION
+  }
+}
+
+// BITS
+
+fn push_bits(bits: &mut Vec<u8>, value: u32, len: usize) {
+  for i in (0..len).rev() {
+    bits.push((value >> i & 1) as u8);
+  }
+}
+
+fn unpack_bits(words: &[u16]) -> Vec<u8> {
+  let mut bits = Vec::with_capacity(words.len() * RADIX_BITS);
+  for &word in words {
+    push_bits(&mut bits, u32::from(word), RADIX_BITS);
+  }
+  bits
+}
+
+fn read_bits(bits: &[u8], start: usize, len: usize) -> u32 {
+  bits[start..start + len]
+    .iter()
+    .fold(0, |acc, bit| acc << 1 | u32::from(*bit))
+}
+
+// CHECKSUM (RS1024)
+
+fn rs1024_polymod(customization: &str, words: &[u16]) -> u32 {
+  const GEN: [u32; 10] = [
+    0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+    0x21b1f890, 0x3f3f120,
+  ];
+  let mut chk: u32 = 1;
+  let values = customization
+    .bytes()
+    .map(u32::from)
+    .chain(words.iter().map(|w| u32::from(*w)));
+  for value in values {
+    let b = chk >> 20;
+    chk = ((chk & 0xfffff) << 10) ^ value;
+    for (i, generator) in GEN.iter().enumerate() {
+      if b >> i & 1 == 1 {
+        chk ^= generator;
+      }
+    }
+  }
+  chk
+}
+
+fn rs1024_checksum(customization: &str, words: &[u16]) -> [u16; CHECKSUM_WORDS] {
+  let mut padded = words.to_vec();
+  padded.extend([0; CHECKSUM_WORDS]);
+  let polymod = rs1024_polymod(customization, &padded) ^ 1;
+  [
+    (polymod >> 20 & 1023) as u16,
+    (polymod >> 10 & 1023) as u16,
+    (polymod & 1023) as u16,
+  ]
+}
+
+// ENCRYPTION (4-round Feistel over PBKDF2-HMAC-SHA256)
+
+fn salt(identifier: u16, extendable: bool) -> Vec<u8> {
+  if extendable {
+    Vec::new()
+  } else {
+    let mut salt = CUSTOMIZATION.as_bytes().to_vec();
+    salt.extend(identifier.to_be_bytes());
+    salt
+  }
+}
+
+fn round_function(
+  round: u8,
+  passphrase: &[u8],
+  exponent: u8,
+  salt: &[u8],
+  r: &[u8],
+) -> Zeroizing<Vec<u8>> {
+  let mut password = Zeroizing::new(vec![round]);
+  password.extend_from_slice(passphrase);
+  let mut message = Zeroizing::new(salt.to_vec());
+  message.extend_from_slice(r);
+  let mut out = Zeroizing::new(vec![0u8; r.len()]);
+  let rounds = (BASE_ITERATION_COUNT << exponent) / u32::from(ROUND_COUNT);
+  pbkdf2::pbkdf2_hmac::<Sha256>(&password, &message, rounds, &mut out);
+  out
+}
+
+fn feistel(
+  input: &[u8],
+  passphrase: &[u8],
+  exponent: u8,
+  identifier: u16,
+  extendable: bool,
+  rounds: impl Iterator<Item = u8>,
+) -> Zeroizing<Vec<u8>> {
+  let salt = salt(identifier, extendable);
+  let half = input.len() / 2;
+  let mut l = Zeroizing::new(input[..half].to_vec());
+  let mut r = Zeroizing::new(input[half..].to_vec());
+  for round in rounds {
+    let f = round_function(round, passphrase, exponent, &salt, &r);
+    let next: Vec<u8> = l.iter().zip(f.iter()).map(|(a, b)| a ^ b).collect();
+    l = std::mem::replace(&mut r, Zeroizing::new(next));
+  }
+  let mut out = r;
+  out.extend_from_slice(&l);
+  out
+}
+
+fn encrypt(
+  secret: &[u8],
+  passphrase: &[u8],
+  exponent: u8,
+  identifier: u16,
+  extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+  feistel(
+    secret,
+    passphrase,
+    exponent,
+    identifier,
+    extendable,
+    0..ROUND_COUNT,
+  )
+}
+
+fn decrypt(
+  encrypted: &[u8],
+  passphrase: &[u8],
+  exponent: u8,
+  identifier: u16,
+  extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+  feistel(
+    encrypted,
+    passphrase,
+    exponent,
+    identifier,
+    extendable,
+    (0..ROUND_COUNT).rev(),
+  )
+}
+
+// SHAMIR SECRET SHARING OVER GF(256)
+
+const fn gf_tables() -> ([u8; 255], [u8; 256]) {
+  let mut exp = [0u8; 255];
+  let mut log = [0u8; 256];
+  let mut poly: u16 = 1;
+  let mut i = 0;
+  while i < 255 {
+    exp[i] = poly as u8;
+    log[poly as usize] = i as u8;
+    // Multiply by the generator x + 1, reducing by x^8 + x^4 + x^3 + x + 1
+    poly ^= poly << 1;
+    if poly & 0x100 != 0 {
+      poly ^= 0x11b;
+    }
+    i += 1;
+  }
+  (exp, log)
+}
+
+const GF_EXP: [u8; 255] = gf_tables().0;
+const GF_LOG: [u8; 256] = gf_tables().1;
+
+fn create_digest(random: &[u8], secret: &[u8]) -> [u8; DIGEST_BYTES] {
+  let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("HMAC takes keys of any size");
+  mac.update(secret);
+  let digest = mac.finalize().into_bytes();
+  let mut out = [0u8; DIGEST_BYTES];
+  out.copy_from_slice(&digest[..DIGEST_BYTES]);
+  out
+}
+
+fn random_bytes(len: usize) -> Zeroizing<Vec<u8>> {
+  let mut rng = rand::rng();
+  Zeroizing::new((0..len).map(|_| rng.random::<u8>()).collect())
+}
+
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Vec<(u8, Zeroizing<Vec<u8>>)> {
+  if threshold == 1 {
+    return (0..count)
+      .map(|i| (i, Zeroizing::new(secret.to_vec())))
+      .collect();
+  }
+
+  let random_count = threshold - 2;
+  let mut shares: Vec<(u8, Zeroizing<Vec<u8>>)> = (0..random_count)
+    .map(|i| (i, random_bytes(secret.len())))
+    .collect();
+
+  let random_part = random_bytes(secret.len() - DIGEST_BYTES);
+  let mut digest_share = Zeroizing::new(create_digest(&random_part, secret).to_vec());
+  digest_share.extend_from_slice(&random_part);
+
+  let mut base: Vec<(u8, &[u8])> = shares.iter().map(|(i, v)| (*i, &v[..])).collect();
+  base.push((DIGEST_INDEX, &digest_share));
+  base.push((SECRET_INDEX, secret));
+
+  let derived: Vec<(u8, Zeroizing<Vec<u8>>)> = (random_count..count)
+    .map(|i| (i, interpolate(&base, i)))
+    .collect();
+  shares.extend(derived);
+  shares
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+  if threshold == 1 {
+    return Ok(Zeroizing::new(shares[0].1.to_vec()));
+  }
+
+  let secret = interpolate(shares, SECRET_INDEX);
+  let digest_share = interpolate(shares, DIGEST_INDEX);
+  let (digest, random_part) = digest_share.split_at(DIGEST_BYTES);
+  if digest != create_digest(random_part, &secret) {
+    bail!("Invalid share digest: a share is wrong or from another backup");
+  }
+  Ok(secret)
+}
+
+/// Lagrange interpolation of the share polynomial at `x`.
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> Zeroizing<Vec<u8>> {
+  if let Some((_, value)) = shares.iter().find(|(i, _)| *i == x) {
+    return Zeroizing::new(value.to_vec());
+  }
+
+  let log = |v: u8| i32::from(GF_LOG[usize::from(v)]);
+  let log_prod: i32 = shares.iter().map(|(i, _)| log(i ^ x)).sum();
+  let mut result = Zeroizing::new(vec![0u8; shares[0].1.len()]);
+
+  for (i, value) in shares {
+    let others: i32 = shares.iter().map(|(j, _)| log(i ^ j)).sum();
+    let basis = (log_prod - log(i ^ x) - others).rem_euclid(255);
+    for (out, &v) in result.iter_mut().zip(value.iter()) {
+      if v != 0 {
+        *out ^= GF_EXP[((log(v) + basis) % 255) as usize];
+      }
+    }
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PASSPHRASE: &[u8] = b"TREZOR";
+
+  fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  fn combine_mnemonics(mnemonics: &[&str], passphrase: &[u8]) -> anyhow::Result<String> {
+    let shares = mnemonics
+      .iter()
+      .map(|m| Share::parse(m))
+      .collect::<anyhow::Result<Vec<_>>>()?;
+    combine(&shares, passphrase).map(|secret| hex(&secret))
+  }
+
+  // Vectors from the SLIP-39 specification (vectors.json)
+
+  #[test]
+  fn single_share_vector() {
+    assert_eq!(
+      combine_mnemonics(
+        &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+        PASSPHRASE
+      )
+      .unwrap(),
+      "bb54aac4b89dc868ba37d9cc21b2cece"
+    );
+  }
+
+  #[test]
+  fn invalid_checksum_vector() {
+    assert!(
+      Share::parse(
+        "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn two_of_three_vector() {
+    let shares = [
+      "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+      "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+    ];
+    assert_eq!(
+      combine_mnemonics(&shares, PASSPHRASE).unwrap(),
+      "b43ceb7e57a0ea8766221624d01b0864"
+    );
+    assert!(combine_mnemonics(&shares[..1], PASSPHRASE).is_err());
+  }
+
+  #[test]
+  fn split_and_combine_groups() {
+    let secret: Vec<u8> = (0..32).collect();
+    let spec = SharingSpec::parse("2:2of3,3of5,1of1").unwrap();
+    let groups = split(&secret, &spec, b"").unwrap();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[1].shares.len(), 5);
+    assert_eq!(groups[0].shares[0].split_whitespace().count(), 33);
+
+    let parse = |group: usize, member: usize| Share::parse(&groups[group].shares[member]).unwrap();
+    let shares = [parse(0, 2), parse(0, 0), parse(2, 0)];
+    assert_eq!(recovery_progress(&shares), (2, 2));
+    assert_eq!(combine(&shares, b"").unwrap().as_slice(), &secret[..]);
+
+    // A wrong passphrase gives another secret, as SLIP-39 intends
+    assert_ne!(combine(&shares, b"other").unwrap().as_slice(), &secret[..]);
+
+    // One member short in the first group
+    let shares = [parse(0, 1), parse(2, 0)];
+    assert_eq!(recovery_progress(&shares), (1, 2));
+    assert!(combine(&shares, b"").is_err());
+  }
+
+  #[test]
+  fn parses_sharing_specs() {
+    assert_eq!(SharingSpec::parse("2of3").unwrap().to_string(), "2of3");
+    assert!(SharingSpec::parse("4of3").is_err());
+    assert!(SharingSpec::parse("1of0").is_err());
+  }
+}
@@ -1,24 +1,24 @@
-use bitcoin::{
-  base58,
-  bip32::{Xpriv, Xpub},
-  hashes::{Hash, sha256d},
-};
-
 use crossterm::{
   event::{Event, KeyCode, read},
-  execute,
   terminal::{disable_raw_mode, enable_raw_mode},
 };
-use std::io::{self, Write};
+use std::{
+  fmt::Write as _,
+  io::{self, Write},
+  path::{Path, PathBuf},
+};
 
 use console::{Color, style};
-use dialoguer::theme::ColorfulTheme;
-use rand::RngExt;
-use sha2::{Digest, Sha256};
-
-use crate::{meta, slogan};
+use dialoguer::{Select, theme::ColorfulTheme};
+use seedctl::{
+  entropy::{DiceStats, EntropySource},
+  hardening::{self, MemoryLock},
+  meta,
+  offline::{self, Finding, OfflinePolicy},
+};
+use zeroize::Zeroizing;
 
-const BITS_PER_DIE: f64 = 2.584962500721156;
+use crate::slogan;
 
 // SLOGAN
 pub fn slogan(show_doc: bool, show_version: bool) {
@@ -57,6 +57,32 @@ pub fn dialoguer_theme(arrow: &str) -> ColorfulTheme {
   theme
 }
 
+/// Shows a `Select` prompt, or returns the default (first) item without
+/// prompting when `use_default` is set (`--yes`).
+pub fn select(use_default: bool, prompt: &str, items: &[&str]) -> usize {
+  if use_default {
+    return 0;
+  }
+
+  Select::with_theme(&dialoguer_theme("►"))
+    .with_prompt(prompt)
+    .items(items)
+    .default(0)
+    .interact()
+    .unwrap()
+}
+
+/// Inserts `-account<N>` before the extension, so one `--export` path can
+/// hold several accounts (`wallet.json` → `wallet-account1.json`).
+pub fn account_file_path(path: &Path, account: u32) -> PathBuf {
+  let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+  let file_name = match path.extension() {
+    Some(ext) => format!("{}-account{}.{}", stem, account, ext.to_string_lossy()),
+    None => format!("{}-account{}", stem, account),
+  };
+  path.with_file_name(file_name)
+}
+
 pub fn exit_confirm() {
   #[cfg(target_os = "windows")]
   {
@@ -71,9 +97,8 @@ pub fn exit_confirm() {
   }
 }
 
-pub fn show_important_card_with_confirm() -> anyhow::Result<bool> {
+pub fn show_important_card() {
   use console::{self, style};
-  use dialoguer::Confirm;
 
   // CLEAR TERMINAL
   // let term = console::Term::stdout();
@@ -104,6 +129,12 @@ pub fn show_important_card_with_confirm() -> anyhow::Result<bool> {
   );
 
   println!();
+}
+
+pub fn show_important_card_with_confirm() -> anyhow::Result<bool> {
+  use dialoguer::Confirm;
+
+  show_important_card();
 
   let confirmed = Confirm::with_theme(&dialoguer_theme("►"))
     .with_prompt("I have read and understood all the recommendations above.")
@@ -131,89 +162,173 @@ pub fn copyright_bottom() {
 
 // NETWORK / SECURITY
 
-pub fn ensure_offline() {
-  use if_addrs::get_if_addrs;
+/// Applies the offline policy: reports every interface or radio that makes
+/// the machine look online, and exits when the policy requires offline.
+pub fn ensure_offline(policy: OfflinePolicy) {
+  if policy == OfflinePolicy::Off {
+    return;
+  }
 
-  let interfaces = match get_if_addrs() {
-    Ok(ifaces) => ifaces,
-    Err(_) => return, // If you can't read it, DO NOT block it.
+  let findings = match offline::check() {
+    Ok(findings) if findings.is_empty() => return,
+    Ok(findings) => findings,
+    // Not being able to look is not proof of being offline
+    Err(e) => vec![Finding {
+      source: "network".to_string(),
+      reason: format!("state could not be read ({})", e),
+    }],
   };
 
-  for iface in interfaces {
-    // ignore loopback
-    if iface.is_loopback() {
-      continue;
-    }
+  let require = policy == OfflinePolicy::Require;
+  let title = if require {
+    console::style("[ SECURITY ABORT ]").bold().red()
+  } else {
+    console::style("[ SECURITY WARNING ]").bold().yellow()
+  };
+  eprintln!(
+    "\n{}\n{}",
+    title,
+    console::style("This machine does not look offline:").yellow()
+  );
+  for finding in &findings {
+    eprintln!(
+      "  ✗ {}: {}",
+      console::style(&finding.source).bold(),
+      finding.reason
+    );
+  }
 
-    // If any non-loopback interface is encountered → ABORT
+  if require {
     eprintln!(
-      "\n{}\n{}\n",
-      console::style("[ SECURITY ABORT ]").bold().red(),
+      "\n{}\n",
       console::style(
-        "Active network interface detected.\n\
-         This program MUST be used offline / air-gapped.\n\n\
-         Disable Wi-Fi, Ethernet, VPNs and try again."
+        "This program MUST be used offline / air-gapped.\n\
+         Disable Wi-Fi, Ethernet, VPNs and Bluetooth (e.g. `rfkill block all`) and try again."
       )
       .yellow()
     );
     copyright_bottom();
     std::process::exit(1);
   }
+  eprintln!(
+    "{}\n",
+    console::style("Continuing anyway; use --require-offline to refuse to run online.").yellow()
+  );
+}
+
+// ENVIRONMENT
+
+/// Locks the secrets in memory, unless `memory` (from
+/// [`hardening::protect_process`]) says it is already, and reports what in
+/// the environment could leak them, right before they are displayed. Interactive runs can stop
+/// here; `--yes` continues after the warning.
+pub fn check_environment(yes: bool, memory: MemoryLock) -> io::Result<()> {
+  let risks = hardening::inspect(hardening::lock_memory(memory));
+  if risks.is_empty() {
+    return Ok(());
+  }
+
+  eprintln!(
+    "\n{}\n{}",
+    console::style("[ ENVIRONMENT WARNING ]").bold().yellow(),
+    console::style("The secrets about to be displayed could leak through:").yellow()
+  );
+  for risk in &risks {
+    eprintln!(
+      "  ✗ {}: {}",
+      console::style(&risk.source).bold(),
+      risk.reason
+    );
+  }
+  eprintln!();
+
+  if yes {
+    return Ok(());
+  }
+  let proceed = dialoguer::Confirm::with_theme(&dialoguer_theme("►"))
+    .with_prompt("Display the wallet anyway?")
+    .default(false)
+    .interact()
+    .map_err(io::Error::other)?;
+  if !proceed {
+    eprintln!("Aborted before displaying the wallet.");
+    copyright_bottom();
+    std::process::exit(1);
+  }
+  Ok(())
 }
 
 // ENTROPY
 
-pub fn read_manual_dice_with_feedback(bits_target: usize) -> Vec<u8> {
+pub fn read_manual_dice_with_feedback(
+  source: EntropySource,
+  bits_target: usize,
+) -> Zeroizing<Vec<u8>> {
   use crossterm::{
     cursor::{Hide, Show},
     execute,
-    terminal::{Clear, ClearType},
   };
 
+  if source.multi_digit() {
+    println!(
+      "\n[ Enter rolls ({}), space or comma between numbers ]",
+      source.label()
+    );
+  } else {
+    println!("\n[ Enter rolls ({}) ]", source.label());
+  }
+
   enable_raw_mode().unwrap();
   execute!(io::stdout(), Hide).unwrap();
 
-  let mut dice: Vec<u8> = Vec::new();
-
-  println!("\n[ Enter dice sequence (1–6) ]");
+  // Sized up front so pushing rolls never reallocates and strands a copy
+  let mut dice: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::with_capacity(1024));
+  // Digits typed for a multi-digit face that is not complete yet
+  let mut pending: Option<u8> = None;
+  let mut drawn = draw_dice_feedback(source, &dice, pending, bits_target, 0);
 
   loop {
     if let Event::Key(event) = read().unwrap() {
       match event.code {
-        KeyCode::Char(c) if ('1'..='6').contains(&c) => {
-          dice.push(c.to_digit(10).unwrap() as u8);
+        KeyCode::Char(c) if source.letter_value(c).is_some() => {
+          dice.extend(pending.take());
+          dice.extend(source.letter_value(c));
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+          let digit = c as u8 - b'0';
+          let value = pending.map_or(Some(digit), |p| {
+            (p * 10 + digit <= source.max_face()).then_some(p * 10 + digit)
+          });
+          match value {
+            Some(v) if source.is_face(v) || (v == 0 && source.multi_digit()) => {
+              // Commit as soon as another digit could not extend the face
+              if v == 0 || v * 10 > source.max_face() {
+                pending = None;
+                dice.extend(source.is_face(v).then_some(v));
+              } else {
+                pending = Some(v);
+              }
+            }
+            _ => {}
+          }
+        }
+        KeyCode::Char(' ') | KeyCode::Char(',') => {
+          dice.extend(pending.take());
+        }
+        KeyCode::Backspace if pending.is_some() => {
+          pending = None;
         }
         KeyCode::Backspace => {
           dice.pop();
         }
         KeyCode::Enter => {
+          dice.extend(pending.take());
           break;
         }
         _ => {}
       }
 
-      let dice_count = dice.len();
-      let bits = (dice_count as f64) * BITS_PER_DIE;
-      let ready = bits >= bits_target as f64;
-
-      let status = if ready {
-        "✔ enough"
-      } else {
-        "… not enough"
-      };
-
-      let dice_str: String = dice.iter().map(|d| char::from(b'0' + *d)).collect();
-
-      // Rewrite ONLY the current line
-      print!("\r");
-      execute!(io::stdout(), Clear(ClearType::CurrentLine)).unwrap();
-
-      print!(
-        "> Dice: {:3} | Bits: {:7.2} / {:3} | {} | [{}]",
-        dice_count, bits, bits_target, status, dice_str
-      );
-
-      io::stdout().flush().unwrap();
+      drawn = draw_dice_feedback(source, &dice, pending, bits_target, drawn);
     }
   }
 
@@ -224,93 +339,331 @@ pub fn read_manual_dice_with_feedback(bits_target: usize) -> Vec<u8> {
   dice
 }
 
-pub fn dice_hash(dice: &[u8]) -> Vec<u8> {
-  Sha256::digest(dice).to_vec()
-}
+/// Most recent rolls shown on the status line; longer sequences would wrap
+/// and break the redraw. The full sequence is confirmed afterwards.
+const DICE_TAIL: usize = 40;
+/// Bytes reserved for a histogram line: two columns of a face, a bar of
+/// three-byte blocks and a count.
+const HISTOGRAM_LINE: usize = 160;
+
+/// Redraws the status line, face histogram and quality warnings in place of
+/// the `previous` lines drawn before. Returns the number of lines drawn.
+fn draw_dice_feedback(
+  source: EntropySource,
+  dice: &[u8],
+  pending: Option<u8>,
+  bits_target: usize,
+  previous: usize,
+) -> usize {
+  use crossterm::{
+    cursor::MoveUp,
+    execute,
+    terminal::{Clear, ClearType},
+  };
 
-pub fn generate_system_entropy(bytes: usize) -> Vec<u8> {
-  let mut rng = rand::rng();
-  (0..bytes).map(|_| rng.random::<u8>()).collect()
-}
+  let stats = DiceStats::from_rolls(source, dice);
+  let bits = (dice.len() as f64) * source.bits_per_roll();
 
-pub fn combine_entropy(a: &[u8], b: &[u8]) -> Vec<u8> {
-  let mut hasher = Sha256::new();
-  hasher.update(a);
-  hasher.update(b);
-  hasher.finalize().to_vec()
-}
+  let status = match (bits >= bits_target as f64, stats.is_suspicious()) {
+    (true, false) => style("✔ enough").green(),
+    (true, true) => style("⚠ enough, but see warnings").yellow(),
+    (false, _) => style("… not enough").dim(),
+  };
 
-pub fn format_key_origin(fingerprint: [u8; 4], purpose: u32, coin_type: u32) -> String {
-  format!(
-    "[{:02x}{:02x}{:02x}{:02x}/{}h/{}h/0h]",
-    fingerprint[0], fingerprint[1], fingerprint[2], fingerprint[3], purpose, coin_type
-  )
-}
+  let skip = dice.len().saturating_sub(DICE_TAIL);
+  // Sized up front (up to three bytes per roll, plus the ellipsis and the
+  // pending digit) so that appending never reallocates and strands a copy
+  let mut tail = Zeroizing::new(String::with_capacity(DICE_TAIL * 3 + 16));
+  if skip > 0 {
+    tail.push('…');
+  }
+  tail.push_str(&source.format_rolls(&dice[skip..]));
+  if let Some(p) = pending {
+    let _ = write!(tail, " {}_", p);
+  }
 
-pub fn output_descriptor(purpose: u32, key_origin: &str, xpub: &str, chain: u32) -> String {
-  match purpose {
-    // BIP84
-    84 => format!("wpkh({}{}/{chain}/*)", key_origin, xpub),
-    // BIP49
-    49 => format!("sh(wpkh({}{}/{chain}/*))", key_origin, xpub),
-    // BIP44
-    44 => format!("pkh({}{}/{chain}/*)", key_origin, xpub),
-    _ => unreachable!(),
+  // Every line says something about the rolls, so none outlives the draw
+  let mut lines = vec![Zeroizing::new(format!(
+    "> Rolls: {:3} | Bits: {:7.2} / {:3} | {} | [{}]",
+    dice.len(),
+    bits,
+    bits_target,
+    status,
+    tail.as_str()
+  ))];
+
+  // Large dice get two histogram columns to keep the block short
+  let columns = if stats.counts.len() > 6 { 2 } else { 1 };
+  let rows = stats.counts.len().div_ceil(columns);
+  let width = 24 / columns;
+  let max = stats.counts.iter().copied().max().unwrap_or(0).max(1);
+  for row in 0..rows {
+    let mut line = Zeroizing::new(String::with_capacity(HISTOGRAM_LINE));
+    line.push(' ');
+    for i in (row..stats.counts.len()).step_by(rows) {
+      let count = stats.counts[i];
+      let bar = Zeroizing::new("█".repeat(count * width / max));
+      let _ = write!(
+        line,
+        "{}{:>2} {:<width$} {:3}",
+        if i == row { " " } else { "    " },
+        usize::from(source.min_face()) + i,
+        bar.as_str(),
+        count
+      );
+    }
+    lines.push(line);
   }
-}
 
-pub fn truncate_entropy(entropy: &[u8], bits: usize) -> Vec<u8> {
-  entropy[..bits / 8].to_vec()
-}
+  lines.push(Zeroizing::new(match stats.chi_squared {
+    Some(chi) => format!(
+      "  Chi-squared: {:6.2} (limit {:.2}) | Longest run: {}",
+      chi,
+      source.chi_squared_critical(),
+      stats.longest_run
+    ),
+    None => format!(
+      "  Chi-squared: needs {} rolls | Longest run: {}",
+      source.min_rolls_for_chi_squared(),
+      stats.longest_run
+    ),
+  }));
+
+  for warning in &stats.warnings {
+    let mut line = Zeroizing::new(String::new());
+    let _ = write!(line, "  {}", style(format_args!("⚠ {}", warning)).yellow());
+    lines.push(line);
+  }
 
-pub fn required_dice(bits: usize) -> usize {
-  ((bits as f64) / BITS_PER_DIE).ceil() as usize
-}
+  let mut stdout = io::stdout();
+  if previous > 1 {
+    execute!(stdout, MoveUp((previous - 1) as u16)).unwrap();
+  }
+  print!("\r");
+  execute!(stdout, Clear(ClearType::FromCursorDown)).unwrap();
+  for (i, line) in lines.iter().enumerate() {
+    print!("{}{}", if i > 0 { "\r\n" } else { "" }, line.as_str());
+  }
+  stdout.flush().unwrap();
 
-pub fn generate_random_dice(count: usize) -> Vec<u8> {
-  let mut rng = rand::rng();
-  (0..count).map(|_| rng.random_range(1..=6)).collect()
+  lines.len()
 }
 
-// KEY FORMATTERS
+/// Rolls per row when two entries are compared.
+const DIFF_ROW: usize = 10;
+/// Mismatches listed one by one; the rest are only counted.
+const DIFF_LIST: usize = 10;
+
+/// Double entry: the rolls are typed a second time and compared position by
+/// position. Mismatches are shown and can be fixed one position at a time,
+/// or either entry typed again. Returns the rolls both entries agree on.
+pub fn verify_manual_dice(
+  source: EntropySource,
+  bits_target: usize,
+  mut first: Zeroizing<Vec<u8>>,
+) -> Zeroizing<Vec<u8>> {
+  println!(
+    "\n{}",
+    style("[ Verification: type the same rolls again ]").bold()
+  );
+  let mut second = read_manual_dice_with_feedback(source, bits_target);
+
+  loop {
+    if first == second {
+      println!(
+        "{} Both entries match ({} rolls)\n",
+        style("✔").green(),
+        first.len()
+      );
+      return first;
+    }
+
+    print_dice_diff(source, &first, &second);
+
+    // Positions only line up when no roll was skipped or doubled; otherwise
+    // the shorter entry gets the missing roll or the longer one loses one
+    let fixes: &[DiceFix] = if first.len() == second.len() {
+      &[
+        DiceFix::Position,
+        DiceFix::RetypeSecond,
+        DiceFix::RetypeBoth,
+      ]
+    } else {
+      &[
+        DiceFix::Insert,
+        DiceFix::Delete,
+        DiceFix::RetypeSecond,
+        DiceFix::RetypeBoth,
+      ]
+    };
+    let labels: Vec<&str> = fixes.iter().map(|f| f.label()).collect();
+    let (shorter, longer) = if first.len() < second.len() {
+      (&mut first, &mut second)
+    } else {
+      (&mut second, &mut first)
+    };
+
+    match fixes[select(false, "[ The entries differ ]", &labels)] {
+      DiceFix::Position => fix_dice_position(source, &mut first, &mut second),
+      DiceFix::Insert => {
+        let suggested = first_mismatch(shorter, longer);
+        let position =
+          read_dice_position("Insert the missing roll at", shorter.len() + 1, suggested);
+        let roll = read_dice_roll(source, &format!("Missing roll at #{}", position));
+        // Capacity was reserved when the rolls were read, so this does not
+        // reallocate and strand a copy
+        shorter.insert(position - 1, roll);
+      }
+      DiceFix::Delete => {
+        let suggested = first_mismatch(shorter, longer);
+        let position = read_dice_position("Delete the extra roll at", longer.len(), suggested);
+        longer.remove(position - 1);
+      }
+      DiceFix::RetypeSecond => {
+        println!("\n{}", style("[ Second entry ]").bold());
+        second = read_manual_dice_with_feedback(source, bits_target);
+      }
+      DiceFix::RetypeBoth => {
+        println!("\n{}", style("[ First entry ]").bold());
+        first = read_manual_dice_with_feedback(source, bits_target);
+        println!("\n{}", style("[ Second entry ]").bold());
+        second = read_manual_dice_with_feedback(source, bits_target);
+      }
+    }
+  }
+}
 
-pub fn xprv_to_zprv(xprv: &Xpriv) -> String {
-  let mut data = xprv.encode();
-  data[0..4].copy_from_slice(&[0x04, 0xB2, 0x43, 0x0C]);
-  base58::encode_check(&data)
+/// Ways to reconcile two dice entries that differ.
+#[derive(Clone, Copy)]
+enum DiceFix {
+  Position,
+  Insert,
+  Delete,
+  RetypeSecond,
+  RetypeBoth,
 }
 
-pub fn xpub_to_zpub(xpub: &Xpub) -> String {
-  let mut data = xpub.encode();
-  data[0..4].copy_from_slice(&[0x04, 0xB2, 0x47, 0x46]);
-  base58::encode_check(&data)
+impl DiceFix {
+  fn label(self) -> &'static str {
+    match self {
+      DiceFix::Position => "Fix a position",
+      DiceFix::Insert => "Insert a missing roll into the shorter entry",
+      DiceFix::Delete => "Delete an extra roll from the longer entry",
+      DiceFix::RetypeSecond => "Type the second entry again",
+      DiceFix::RetypeBoth => "Type both entries again",
+    }
+  }
 }
 
-pub fn xprv_to_yprv(xprv: &Xpriv) -> String {
-  let mut data = xprv.encode();
-  // yprv prefix
-  data[0..4].copy_from_slice(&[0x04, 0x9D, 0x78, 0x78]);
-  base58::encode_check(&data)
+/// First position (1-based) where the entries disagree.
+fn first_mismatch(a: &[u8], b: &[u8]) -> usize {
+  (0..a.len().max(b.len()))
+    .find(|&i| a.get(i) != b.get(i))
+    .map_or(1, |i| i + 1)
 }
 
-/// Converte xpub → ypub / zpub (SLIP-132)
-pub fn convert_xpub_prefix(xpub: &Xpub, version: u32) -> String {
-  // Decode Base58Check
-  let mut data = base58::decode_check(&xpub.to_string()).expect("Invalid Base58Check xpub");
+/// Prints the first entry in rows with the mismatched positions
+/// highlighted, followed by the mismatches themselves.
+fn print_dice_diff(source: EntropySource, first: &[u8], second: &[u8]) {
+  let width = if source.multi_digit() { 2 } else { 1 };
+  let length = first.len().max(second.len());
+  let mismatches: Vec<usize> = (0..length)
+    .filter(|&i| first.get(i) != second.get(i))
+    .collect();
 
-  // Substitui version bytes
-  data[0..4].copy_from_slice(&version.to_be_bytes());
+  println!(
+    "\n{} {} of {} positions differ",
+    style("✗").red(),
+    mismatches.len(),
+    length
+  );
+  if first.len() != second.len() {
+    println!(
+      "{}",
+      style(format!(
+        "The first entry has {} rolls and the second {}: a roll was probably skipped or \
+         doubled near #{}.",
+        first.len(),
+        second.len(),
+        mismatches[0] + 1
+      ))
+      .yellow()
+    );
+  }
+  println!();
 
-  // Recalcula checksum
-  let checksum = sha256d::Hash::hash(&data[..data.len() - 4]);
+  for start in (0..first.len()).step_by(DIFF_ROW) {
+    let end = (start + DIFF_ROW).min(first.len());
+    let cells: Vec<Zeroizing<String>> = (start..end)
+      .map(|i| {
+        let cell = Zeroizing::new(format!("{:>width$}", first[i], width = width));
+        if mismatches.contains(&i) {
+          Zeroizing::new(style(cell.as_str()).bold().red().underlined().to_string())
+        } else {
+          cell
+        }
+      })
+      .collect();
+    print!("  {}", style(format!("#{:>3}-{:<3}", start + 1, end)).dim());
+    for cell in &cells {
+      print!(" {}", cell.as_str());
+    }
+    println!();
+  }
+  println!();
+
+  let show = |roll: Option<&u8>| Zeroizing::new(roll.map_or("-".to_string(), |r| r.to_string()));
+  for &i in mismatches.iter().take(DIFF_LIST) {
+    println!(
+      "  #{:<3} first {:>2}  second {:>2}",
+      i + 1,
+      show(first.get(i)).as_str(),
+      show(second.get(i)).as_str()
+    );
+  }
+  if mismatches.len() > DIFF_LIST {
+    println!("  … and {} more", mismatches.len() - DIFF_LIST);
+  }
+  println!();
+}
 
-  let len = data.len();
-  data[len - 4..len].copy_from_slice(&checksum[..4]);
+/// Asks for a position and the roll that belongs there, and writes it into
+/// both entries. Both entries have the same length.
+fn fix_dice_position(source: EntropySource, first: &mut [u8], second: &mut [u8]) {
+  let position = read_dice_position(
+    "Position to fix",
+    first.len(),
+    first_mismatch(first, second),
+  );
+  let roll = read_dice_roll(source, &format!("Correct roll at #{}", position));
+  first[position - 1] = roll;
+  second[position - 1] = roll;
+}
 
-  // Encode Base58Check
-  base58::encode_check(&data)
+/// A position between 1 and `last`.
+fn read_dice_position(prompt: &str, last: usize, suggested: usize) -> usize {
+  dialoguer::Input::<usize>::with_theme(&dialoguer_theme("►"))
+    .with_prompt(prompt)
+    .default(suggested.min(last))
+    .validate_with(|position: &usize| {
+      if (1..=last).contains(position) {
+        Ok(())
+      } else {
+        Err(format!("Enter a position between 1 and {}", last))
+      }
+    })
+    .interact_text()
+    .unwrap()
 }
 
-pub fn xpub_to_ypub(xpub: &Xpub) -> String {
-  convert_xpub_prefix(xpub, 0x049d7cb2) // ypub
+/// A single roll, including the two-digit faces of large dice.
+fn read_dice_roll(source: EntropySource, prompt: &str) -> u8 {
+  let input = Zeroizing::new(
+    dialoguer::Input::<String>::with_theme(&dialoguer_theme("►"))
+      .with_prompt(format!("{} ({})", prompt, source.label()))
+      .validate_with(|input: &String| source.parse_roll(input.trim()).map(|_| ()))
+      .interact_text()
+      .unwrap(),
+  );
+  source.parse_roll(input.trim()).unwrap()
 }
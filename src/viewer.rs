@@ -0,0 +1,251 @@
+//! Secure viewer: secrets on the alternate screen, hidden until a key is
+//! pressed, with the screen and scrollback wiped on exit.
+
+use console::{measure_text_width, style};
+use crossterm::{
+  cursor::{Hide, MoveTo, Show},
+  event::{Event, KeyCode, KeyEventKind, KeyModifiers, read},
+  execute, queue,
+  terminal::{
+    Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+    enable_raw_mode, size,
+  },
+};
+use std::{
+  io::{self, Write},
+  ops::Range,
+};
+use zeroize::Zeroizing;
+
+/// Shown instead of a hidden value; fixed, so it gives away no length.
+const HIDDEN: &str = "••••••••";
+/// Header, blank line, blank line before the footer, footer.
+const CHROME_ROWS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayMode {
+  /// As many items as fit on the screen, revealed one by one.
+  Pages,
+  /// One word (or key, or share) per screen.
+  Words,
+  /// Printed with the rest of the output, as before.
+  Plain,
+}
+
+impl DisplayMode {
+  pub const ALL: [DisplayMode; 3] = [DisplayMode::Pages, DisplayMode::Words, DisplayMode::Plain];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      DisplayMode::Pages => "pages",
+      DisplayMode::Words => "words",
+      DisplayMode::Plain => "plain",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|m| m.name() == name)
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      DisplayMode::Pages => "Secure viewer, a page at a time",
+      DisplayMode::Words => "Secure viewer, one word at a time",
+      DisplayMode::Plain => "Print to the terminal (stays in the scrollback)",
+    }
+  }
+
+  pub fn is_secure(self) -> bool {
+    self != DisplayMode::Plain
+  }
+}
+
+/// A titled group of secrets; pages never span two sections.
+pub struct Section {
+  pub title: String,
+  pub items: Vec<Secret>,
+}
+
+/// One value to reveal. Values with several lines (a SeedQR) are drawn
+/// below their label, others next to it.
+pub struct Secret {
+  pub label: String,
+  pub value: Zeroizing<String>,
+}
+
+struct Page {
+  section: usize,
+  items: Range<usize>,
+}
+
+/// Alternate screen in raw mode for as long as it lives. Dropping it clears
+/// the alternate screen, the main screen and the scrollback.
+struct Screen;
+
+impl Screen {
+  fn enter() -> io::Result<Self> {
+    enable_raw_mode()?;
+    let screen = Screen;
+    execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+    Ok(screen)
+  }
+}
+
+impl Drop for Screen {
+  fn drop(&mut self) {
+    let _ = execute!(
+      io::stdout(),
+      Clear(ClearType::All),
+      LeaveAlternateScreen,
+      Show,
+      Clear(ClearType::All),
+      Clear(ClearType::Purge),
+      MoveTo(0, 0)
+    );
+    let _ = disable_raw_mode();
+  }
+}
+
+/// Shows the sections until the user closes the viewer.
+pub fn show(mode: DisplayMode, sections: &[Section]) -> io::Result<()> {
+  let mut out = io::stdout();
+  let _screen = Screen::enter()?;
+
+  let mut pages = paginate(mode, sections, size()?);
+  let mut page = 0;
+  let mut revealed = 0;
+
+  loop {
+    draw(&mut out, sections, &pages, page, revealed)?;
+    match read()? {
+      Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+        KeyCode::Char(' ') | KeyCode::Enter => {
+          if revealed < pages[page].items.len() {
+            revealed += 1;
+          } else if page + 1 < pages.len() {
+            page += 1;
+            revealed = 0;
+          }
+        }
+        KeyCode::Right | KeyCode::PageDown | KeyCode::Char('n') if page + 1 < pages.len() => {
+          page += 1;
+          revealed = 0;
+        }
+        KeyCode::Left | KeyCode::PageUp | KeyCode::Char('p') if page > 0 => {
+          page -= 1;
+          revealed = 0;
+        }
+        KeyCode::Char('h') => revealed = 0,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+        KeyCode::Char('q') | KeyCode::Esc => break,
+        _ => {}
+      },
+      Event::Resize(..) => {
+        pages = paginate(mode, sections, size()?);
+        page = page.min(pages.len() - 1);
+        revealed = 0;
+      }
+      _ => {}
+    }
+  }
+
+  Ok(())
+}
+
+/// Splits the sections into pages that fit a `(columns, rows)` terminal.
+/// A page always holds at least one item, even if it does not fit.
+fn paginate(mode: DisplayMode, sections: &[Section], (columns, rows): (u16, u16)) -> Vec<Page> {
+  let available = usize::from(rows).saturating_sub(CHROME_ROWS).max(1);
+  let mut pages = Vec::new();
+
+  for (s, section) in sections.iter().enumerate() {
+    let mut start = 0;
+    let mut used = 0;
+    for (i, item) in section.items.iter().enumerate() {
+      let height = item_rows(item, columns);
+      let full = mode == DisplayMode::Words || used + height > available;
+      if i > start && full {
+        pages.push(Page {
+          section: s,
+          items: start..i,
+        });
+        start = i;
+        used = 0;
+      }
+      used += height;
+    }
+    if start < section.items.len() {
+      pages.push(Page {
+        section: s,
+        items: start..section.items.len(),
+      });
+    }
+  }
+
+  pages
+}
+
+/// Terminal rows an item takes once revealed, with long lines wrapped.
+fn item_rows(item: &Secret, columns: u16) -> usize {
+  let wrapped = |width: usize| width.div_ceil(usize::from(columns).max(1)).max(1);
+  if item.value.contains('\n') {
+    1 + item
+      .value
+      .lines()
+      .map(|line| wrapped(measure_text_width(line)))
+      .sum::<usize>()
+  } else {
+    wrapped(measure_text_width(&item.label) + 2 + measure_text_width(&item.value))
+  }
+}
+
+fn draw(
+  out: &mut impl Write,
+  sections: &[Section],
+  pages: &[Page],
+  page: usize,
+  revealed: usize,
+) -> io::Result<()> {
+  let current = &pages[page];
+  let section = &sections[current.section];
+  let (_, rows) = size()?;
+
+  queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+  write!(
+    out,
+    "{}  {}\r\n\r\n",
+    style(&section.title).bold().blue(),
+    style(format!("page {} of {}", page + 1, pages.len())).dim()
+  )?;
+
+  for (n, item) in section.items[current.items.clone()].iter().enumerate() {
+    let shown = n < revealed;
+    if item.value.contains('\n') {
+      write!(out, "{}\r\n", style(&item.label).bold())?;
+      if shown {
+        for line in item.value.lines() {
+          write!(out, "{}\r\n", line)?;
+        }
+      } else {
+        write!(out, "{}\r\n", style(HIDDEN).dim())?;
+      }
+    } else {
+      let value = if shown {
+        Zeroizing::new(style(item.value.as_str()).bold().yellow().to_string())
+      } else {
+        Zeroizing::new(style(HIDDEN).dim().to_string())
+      };
+      write!(out, "{}  {}\r\n", style(&item.label).bold(), value.as_str())?;
+    }
+  }
+
+  let last = page + 1 == pages.len() && revealed == current.items.len();
+  let help = if last {
+    "h hide · ←/→ page · q close (clears the screen)"
+  } else {
+    "Space/Enter reveal next · h hide · ←/→ page · q close"
+  };
+  queue!(out, MoveTo(0, rows.saturating_sub(1)))?;
+  write!(out, "{}", style(help).dim())?;
+  out.flush()
+}
@@ -0,0 +1,681 @@
+use anyhow::Context;
+use bip39::Mnemonic;
+use bitcoin::{
+  Address, CompressedPublicKey, Network, PublicKey,
+  bip32::{ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub},
+  secp256k1::Secp256k1,
+};
+
+use crate::{
+  entropy::DiceOrigin,
+  export::{self, ExportFormat},
+  keys::{self, SecretXpriv},
+  meta, mnemonic,
+};
+use zeroize::Zeroizing;
+
+/// Number of receive addresses shown by default.
+pub const DEFAULT_ADDRESS_COUNT: u32 = 10;
+
+/// Most addresses derived per chain; each one takes a few milliseconds and
+/// is printed, so larger counts are almost certainly a typo.
+pub const MAX_ADDRESS_COUNT: u32 = 10_000;
+
+/// Highest BIP32 account index (accounts are hardened children).
+pub const MAX_ACCOUNT: u32 = (1 << 31) - 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptType {
+  /// Native SegWit (P2WPKH)
+  Bip84,
+  /// Nested SegWit (P2SH-P2WPKH)
+  Bip49,
+  /// Legacy (P2PKH)
+  Bip44,
+  /// Taproot (P2TR, key path only)
+  Bip86,
+}
+
+impl ScriptType {
+  pub fn purpose(self) -> u32 {
+    match self {
+      ScriptType::Bip84 => 84,
+      ScriptType::Bip49 => 49,
+      ScriptType::Bip44 => 44,
+      ScriptType::Bip86 => 86,
+    }
+  }
+
+  /// Output type name used by Coldcard/Sparrow exports.
+  pub fn output_name(self) -> &'static str {
+    match self {
+      ScriptType::Bip84 => "p2wpkh",
+      ScriptType::Bip49 => "p2sh-p2wpkh",
+      ScriptType::Bip44 => "p2pkh",
+      ScriptType::Bip86 => "p2tr",
+    }
+  }
+
+  /// Name used on the command line and in the export JSON.
+  pub fn name(self) -> &'static str {
+    match self {
+      ScriptType::Bip84 => "bip84",
+      ScriptType::Bip49 => "bip49",
+      ScriptType::Bip44 => "bip44",
+      ScriptType::Bip86 => "bip86",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "bip84" => Some(ScriptType::Bip84),
+      "bip49" => Some(ScriptType::Bip49),
+      "bip44" => Some(ScriptType::Bip44),
+      "bip86" => Some(ScriptType::Bip86),
+      _ => None,
+    }
+  }
+
+  pub fn address_label(self) -> &'static str {
+    match self {
+      ScriptType::Bip84 => "Address BIP84 (Native SegWit)",
+      ScriptType::Bip49 => "Address BIP49 (Nested SegWit)",
+      ScriptType::Bip44 => "Address BIP44 (Legacy)",
+      ScriptType::Bip86 => "Address BIP86 (Taproot)",
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressChain {
+  /// External chain (`/0/i`)
+  Receive,
+  /// Internal chain (`/1/i`)
+  Change,
+  Both,
+}
+
+impl AddressChain {
+  /// BIP32 chain numbers covered, in display order.
+  pub fn chains(self) -> &'static [u32] {
+    match self {
+      AddressChain::Receive => &[0],
+      AddressChain::Change => &[1],
+      AddressChain::Both => &[0, 1],
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "receive" => Some(AddressChain::Receive),
+      "change" => Some(AddressChain::Change),
+      "both" => Some(AddressChain::Both),
+      _ => None,
+    }
+  }
+}
+
+/// Which addresses to derive: `count` indexes from `start` on each chain.
+#[derive(Clone, Copy, Debug)]
+pub struct AddressRange {
+  pub start: u32,
+  pub count: u32,
+  pub chain: AddressChain,
+}
+
+impl Default for AddressRange {
+  fn default() -> Self {
+    AddressRange {
+      start: 0,
+      count: DEFAULT_ADDRESS_COUNT,
+      chain: AddressChain::Receive,
+    }
+  }
+}
+
+pub fn coin_type(network: Network) -> u32 {
+  match network {
+    Network::Bitcoin => 0,
+    _ => 1,
+  }
+}
+
+pub fn network_name(network: Network) -> &'static str {
+  match network {
+    Network::Bitcoin => "bitcoin",
+    Network::Testnet => "testnet",
+    _ => "unknown",
+  }
+}
+
+/// What the BIP32 master key is built from. Secrets are wiped when dropped.
+#[derive(Clone)]
+pub enum WalletSeed {
+  /// A BIP39 mnemonic and its passphrase, stretched into the seed.
+  Mnemonic {
+    mnemonic: Mnemonic,
+    passphrase: Zeroizing<String>,
+  },
+  /// A SLIP-39 master secret, used as the seed as it is.
+  MasterSecret(Zeroizing<Vec<u8>>),
+}
+
+impl WalletSeed {
+  /// The seed handed to BIP32: 64 bytes for a mnemonic, the master secret
+  /// otherwise.
+  pub fn to_seed(&self) -> Zeroizing<Vec<u8>> {
+    match self {
+      WalletSeed::Mnemonic {
+        mnemonic,
+        passphrase,
+      } => {
+        let seed = Zeroizing::new(mnemonic.to_seed(passphrase.as_str()));
+        Zeroizing::new(seed.to_vec())
+      }
+      WalletSeed::MasterSecret(secret) => secret.clone(),
+    }
+  }
+
+  pub fn mnemonic(&self) -> Option<&Mnemonic> {
+    match self {
+      WalletSeed::Mnemonic { mnemonic, .. } => Some(mnemonic),
+      WalletSeed::MasterSecret(_) => None,
+    }
+  }
+}
+
+/// Everything needed to derive a single-sig account. Secret inputs are
+/// wiped when dropped.
+pub struct WalletParams {
+  pub seed: WalletSeed,
+  pub network: Network,
+  pub script_type: ScriptType,
+  pub account: u32,
+  pub addresses: AddressRange,
+  /// Set when the mnemonic was generated from dice in this session.
+  pub dice: Option<DiceOrigin>,
+}
+
+pub struct DerivedAddress {
+  pub chain: u32,
+  pub index: u32,
+  pub path: String,
+  pub address: Address,
+}
+
+/// Result of [`derive_wallet`]: account keys, descriptors and the requested
+/// addresses of `m/purpose'/coin'/account'`.
+pub struct DerivedWallet {
+  /// `None` when the wallet comes from a SLIP-39 master secret.
+  pub mnemonic: Option<Mnemonic>,
+  pub network: Network,
+  pub script_type: ScriptType,
+  pub account: u32,
+  pub fingerprint: Fingerprint,
+  pub derivation_path: DerivationPath,
+  pub account_xprv: SecretXpriv,
+  pub account_xpub: Xpub,
+  pub descriptor_receive: String,
+  pub descriptor_change: String,
+  pub addresses: Vec<DerivedAddress>,
+  pub dice: Option<DiceOrigin>,
+}
+
+pub fn derive_wallet(params: WalletParams) -> anyhow::Result<DerivedWallet> {
+  let WalletParams {
+    seed,
+    network,
+    script_type,
+    account,
+    addresses: range,
+    dice,
+  } = params;
+
+  if account > MAX_ACCOUNT {
+    anyhow::bail!(
+      "Account index {} is out of range (0-{})",
+      account,
+      MAX_ACCOUNT
+    );
+  }
+
+  let purpose = script_type.purpose();
+  let coin_type = coin_type(network);
+
+  let mnemonic = seed.mnemonic().cloned();
+  let seed = seed.to_seed();
+
+  let secp = Secp256k1::new();
+  let master = SecretXpriv(Xpriv::new_master(network, &seed[..]).context("Invalid master seed")?);
+
+  let path: DerivationPath = format!("m/{}'/{}'/{}'", purpose, coin_type, account).parse()?;
+  let account_xprv = SecretXpriv(master.derive_priv(&secp, &path)?);
+  let account_xpub = Xpub::from_priv(&secp, &account_xprv);
+
+  let fingerprint = master.fingerprint(&secp);
+  let key_origin = keys::format_key_origin(fingerprint.to_bytes(), purpose, coin_type, account);
+  let xpub = account_xpub.to_string();
+
+  let descriptor_receive = keys::output_descriptor(purpose, &key_origin, &xpub, 0);
+  let descriptor_change = keys::output_descriptor(purpose, &key_origin, &xpub, 1);
+
+  if range.count > MAX_ADDRESS_COUNT {
+    anyhow::bail!(
+      "Address count {} exceeds the maximum of {}",
+      range.count,
+      MAX_ADDRESS_COUNT
+    );
+  }
+  // Addresses use non-hardened indexes only
+  let end = range
+    .start
+    .checked_add(range.count)
+    .filter(|end| *end <= 1 << 31)
+    .context("Address range exceeds the maximum non-hardened index")?;
+
+  let mut addresses = Vec::with_capacity(range.count as usize * range.chain.chains().len());
+
+  for &chain in range.chain.chains() {
+    for index in range.start..end {
+      addresses.push(DerivedAddress {
+        chain,
+        index,
+        path: format!(
+          "m/{}'/{}'/{}'/{}/{}",
+          purpose, coin_type, account, chain, index
+        ),
+        address: derive_address(&account_xpub, script_type, network, chain, index)?,
+      });
+    }
+  }
+
+  Ok(DerivedWallet {
+    mnemonic,
+    network,
+    script_type,
+    account,
+    fingerprint,
+    derivation_path: path,
+    account_xprv,
+    account_xpub,
+    descriptor_receive,
+    descriptor_change,
+    addresses,
+    dice,
+  })
+}
+
+/// Master key fingerprint of the mnemonic with `passphrase`, without deriving
+/// a whole wallet. It does not depend on the network.
+pub fn master_fingerprint(mnemonic: &Mnemonic, passphrase: &str) -> anyhow::Result<Fingerprint> {
+  let seed = Zeroizing::new(mnemonic.to_seed(passphrase));
+  let master =
+    SecretXpriv(Xpriv::new_master(Network::Bitcoin, &seed[..]).context("Invalid master seed")?);
+  Ok(master.fingerprint(&Secp256k1::new()))
+}
+
+/// Address at `account_xpub/chain/index` for the given script type.
+pub fn derive_address(
+  account_xpub: &Xpub,
+  script_type: ScriptType,
+  network: Network,
+  chain: u32,
+  index: u32,
+) -> anyhow::Result<Address> {
+  let secp = Secp256k1::verification_only();
+  let child = account_xpub.derive_pub(
+    &secp,
+    &[
+      ChildNumber::from_normal_idx(chain)?,
+      ChildNumber::from_normal_idx(index)?,
+    ],
+  )?;
+
+  let address = match script_type {
+    // BIP84 – Native SegWit
+    ScriptType::Bip84 => Address::p2wpkh(&CompressedPublicKey(child.public_key), network),
+    // BIP49 – Nested SegWit
+    ScriptType::Bip49 => Address::p2shwpkh(&CompressedPublicKey(child.public_key), network),
+    // BIP44 – Legacy
+    ScriptType::Bip44 => Address::p2pkh(PublicKey::new(child.public_key), network),
+    // BIP86 – Taproot (no script tree)
+    ScriptType::Bip86 => Address::p2tr(&secp, child.to_x_only_pub(), None, network),
+  };
+
+  Ok(address)
+}
+
+impl DerivedWallet {
+  /// Account path in the `m/84'/0'/0'` notation.
+  pub fn derivation_path_string(&self) -> String {
+    format!("m/{}", self.derivation_path)
+  }
+
+  /// Account public key with the SLIP-132 prefix of the script type.
+  pub fn slip132_xpub(&self) -> String {
+    keys::slip132_xpub(&self.account_xpub, self.script_type)
+  }
+
+  /// Account private key with the SLIP-132 prefix of the script type.
+  pub fn slip132_xprv(&self) -> Zeroizing<String> {
+    keys::slip132_xprv(&self.account_xprv, self.script_type)
+  }
+
+  pub fn to_export(&self, watch_only: bool) -> export::WalletExport {
+    export::WalletExport {
+      software: export::SoftwareInfo {
+        name: "seedctl".to_string(),
+        version: meta::VERSION.to_string(),
+        repository: meta::PROJECT_REPOSITORY.to_string(),
+      },
+      network: network_name(self.network).to_string(),
+      script_type: self.script_type.name().to_string(),
+      key_origin: export::KeyOrigin {
+        fingerprint: self.fingerprint.to_string(),
+        derivation_path: self.derivation_path_string(),
+      },
+      mnemonic: self.mnemonic.as_ref().map(|m| export::MnemonicInfo {
+        language: mnemonic::language_name(m.language()).to_string(),
+        word_count: m.word_count(),
+        entropy_bits: mnemonic::entropy_bits(m.word_count()),
+      }),
+      watch_only,
+      dice: self.dice.map(|dice| export::DiceInfo {
+        source: dice.source.name().to_string(),
+        scheme: dice.scheme.name().to_string(),
+        mode: if dice.hybrid {
+          "hybrid"
+        } else {
+          "deterministic"
+        }
+        .to_string(),
+        rolls: dice.rolls,
+      }),
+      keys: export::Keys {
+        account_xpub: self.slip132_xpub(),
+        account_xprv: if watch_only {
+          None
+        } else {
+          Some(self.slip132_xprv())
+        },
+      },
+      descriptors: export::Descriptors {
+        receive: self.descriptor_receive.clone(),
+        change: self.descriptor_change.clone(),
+      },
+    }
+  }
+
+  /// Receive and change descriptors as an `importdescriptors` request,
+  /// ready to create a watch-only wallet on an online Bitcoin Core node.
+  pub fn to_core_import(&self) -> Vec<export::CoreImportDescriptor> {
+    [
+      (&self.descriptor_receive, false),
+      (&self.descriptor_change, true),
+    ]
+    .into_iter()
+    .map(|(desc, internal)| export::CoreImportDescriptor {
+      desc: desc.clone(),
+      active: true,
+      internal,
+      range: export::CORE_IMPORT_RANGE,
+      timestamp: "now".to_string(),
+    })
+    .collect()
+  }
+
+  /// Electrum watch-only wallet. Electrum has no Taproot single-sig
+  /// wallets, so BIP86 accounts are rejected.
+  pub fn to_electrum(&self) -> anyhow::Result<export::ElectrumWallet> {
+    if self.script_type == ScriptType::Bip86 {
+      anyhow::bail!("Electrum does not support Taproot (BIP86) wallets");
+    }
+
+    Ok(export::ElectrumWallet {
+      keystore: export::ElectrumKeystore {
+        kind: "bip32".to_string(),
+        xpub: self.slip132_xpub(),
+        derivation: self.derivation_path_string(),
+        root_fingerprint: self.fingerprint.to_string(),
+        label: format!("seedctl {} #{}", self.fingerprint, self.account),
+      },
+      wallet_type: "standard".to_string(),
+      use_encryption: false,
+      seed_version: export::ELECTRUM_SEED_VERSION,
+    })
+  }
+
+  /// Coldcard-style generic JSON, as imported by Sparrow.
+  pub fn to_generic(&self) -> export::GenericWalletExport {
+    let xpub = self.account_xpub.to_string();
+    let slip132 = self.slip132_xpub();
+
+    let account = export::GenericAccount {
+      name: self.script_type.output_name().to_string(),
+      deriv: self.derivation_path_string(),
+      slip132_pub: (slip132 != xpub).then_some(slip132),
+      xpub,
+      desc: self.descriptor_receive.clone(),
+      first: derive_address(&self.account_xpub, self.script_type, self.network, 0, 0)
+        .ok()
+        .map(|a| a.to_string()),
+    };
+
+    export::GenericWalletExport {
+      chain: match self.network {
+        Network::Bitcoin => "BTC".to_string(),
+        _ => "XTN".to_string(),
+      },
+      xfp: self.fingerprint.to_string().to_uppercase(),
+      account: self.account,
+      accounts: [(self.script_type.name().to_string(), account)].into(),
+    }
+  }
+
+  /// Derived addresses with their full paths, for the address list exports.
+  pub fn to_address_list(&self) -> Vec<export::AddressEntry> {
+    self
+      .addresses
+      .iter()
+      .map(|a| export::AddressEntry {
+        account: self.account,
+        chain: if a.chain == 0 { "receive" } else { "change" }.to_string(),
+        index: a.index,
+        path: a.path.clone(),
+        address: a.address.to_string(),
+      })
+      .collect()
+  }
+
+  /// Serializes the wallet in the given export format. Only
+  /// `SeedctlPrivate` carries the account private key; the result is wiped
+  /// on drop either way.
+  pub fn render_export(&self, format: ExportFormat) -> anyhow::Result<Zeroizing<String>> {
+    match format {
+      ExportFormat::Seedctl => to_json(&self.to_export(true)),
+      ExportFormat::SeedctlPrivate => to_json(&self.to_export(false)),
+      ExportFormat::BitcoinCore => to_json(&self.to_core_import()),
+      ExportFormat::Electrum => to_json(&self.to_electrum()?),
+      ExportFormat::Sparrow => to_json(&self.to_generic()),
+      ExportFormat::AddressesJson => to_json(&self.to_address_list()),
+      ExportFormat::AddressesCsv => Ok(Zeroizing::new(export::addresses_to_csv(
+        &self.to_address_list(),
+      ))),
+    }
+  }
+}
+
+/// Pretty JSON written into a pre-sized buffer, so serializing keys does not
+/// leave reallocated copies behind.
+fn to_json<T: serde::Serialize>(value: &T) -> anyhow::Result<Zeroizing<String>> {
+  let mut buf = Zeroizing::new(Vec::with_capacity(16 * 1024));
+  serde_json::to_writer_pretty(&mut *buf, value)?;
+  let json = String::from_utf8(std::mem::take(&mut *buf))?;
+  Ok(Zeroizing::new(json))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Mnemonic of the BIP49/84/86 test vectors.
+  const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+  fn derive(script_type: ScriptType, network: Network) -> DerivedWallet {
+    derive_wallet(WalletParams {
+      seed: WalletSeed::Mnemonic {
+        mnemonic: Mnemonic::parse(MNEMONIC).unwrap(),
+        passphrase: Zeroizing::new(String::new()),
+      },
+      network,
+      script_type,
+      account: 0,
+      addresses: AddressRange {
+        start: 0,
+        count: 1,
+        chain: AddressChain::Both,
+      },
+      dice: None,
+    })
+    .unwrap()
+  }
+
+  fn address(wallet: &DerivedWallet, chain: u32) -> String {
+    wallet
+      .addresses
+      .iter()
+      .find(|a| a.chain == chain && a.index == 0)
+      .unwrap()
+      .address
+      .to_string()
+  }
+
+  #[test]
+  fn bip84_vector() {
+    let wallet = derive(ScriptType::Bip84, Network::Bitcoin);
+    assert_eq!(wallet.derivation_path_string(), "m/84'/0'/0'");
+    assert_eq!(
+      wallet.slip132_xprv().as_str(),
+      "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE"
+    );
+    assert_eq!(
+      wallet.slip132_xpub(),
+      "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+    );
+    assert_eq!(
+      address(&wallet, 0),
+      "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+    assert_eq!(
+      address(&wallet, 1),
+      "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"
+    );
+  }
+
+  #[test]
+  fn bip49_vector() {
+    let wallet = derive(ScriptType::Bip49, Network::Bitcoin);
+    assert!(wallet.slip132_xpub().starts_with("ypub"));
+    assert!(wallet.slip132_xprv().starts_with("yprv"));
+    assert_eq!(address(&wallet, 0), "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf");
+
+    // The published BIP49 vector is on testnet
+    let wallet = derive(ScriptType::Bip49, Network::Testnet);
+    assert_eq!(wallet.derivation_path_string(), "m/49'/1'/0'");
+    assert!(wallet.slip132_xpub().starts_with("upub"));
+    assert_eq!(address(&wallet, 0), "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2");
+  }
+
+  #[test]
+  fn bip44_vector() {
+    let wallet = derive(ScriptType::Bip44, Network::Bitcoin);
+    assert_eq!(
+      wallet.slip132_xpub(),
+      "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+    );
+    assert_eq!(address(&wallet, 0), "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA");
+  }
+
+  #[test]
+  fn bip86_vector() {
+    let wallet = derive(ScriptType::Bip86, Network::Bitcoin);
+    assert_eq!(wallet.derivation_path_string(), "m/86'/0'/0'");
+    assert_eq!(
+      wallet.slip132_xpub(),
+      "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ"
+    );
+    assert_eq!(
+      address(&wallet, 0),
+      "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+    assert_eq!(
+      address(&wallet, 1),
+      "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+    );
+  }
+
+  #[test]
+  fn fingerprint_and_passphrase() {
+    let mnemonic = Mnemonic::parse(MNEMONIC).unwrap();
+    assert_eq!(
+      master_fingerprint(&mnemonic, "").unwrap().to_string(),
+      "73c5da0a"
+    );
+    assert_ne!(
+      master_fingerprint(&mnemonic, "secret").unwrap().to_string(),
+      "73c5da0a"
+    );
+    assert_eq!(
+      derive(ScriptType::Bip84, Network::Bitcoin)
+        .fingerprint
+        .to_string(),
+      "73c5da0a"
+    );
+  }
+
+  #[test]
+  fn master_secret_is_the_bip32_seed() {
+    let wallet = derive_wallet(WalletParams {
+      // BIP32 test vector 1
+      seed: WalletSeed::MasterSecret(Zeroizing::new((0..16).collect())),
+      network: Network::Bitcoin,
+      script_type: ScriptType::Bip44,
+      account: 0,
+      addresses: AddressRange::default(),
+      dice: None,
+    })
+    .unwrap();
+    assert!(wallet.mnemonic.is_none());
+    assert_eq!(wallet.fingerprint.to_string(), "3442193e");
+    assert!(wallet.to_export(true).mnemonic.is_none());
+  }
+
+  #[test]
+  fn rejects_out_of_range_addresses() {
+    let params = |start, count| WalletParams {
+      seed: WalletSeed::Mnemonic {
+        mnemonic: Mnemonic::parse(MNEMONIC).unwrap(),
+        passphrase: Zeroizing::new(String::new()),
+      },
+      network: Network::Bitcoin,
+      script_type: ScriptType::Bip84,
+      account: 0,
+      addresses: AddressRange {
+        start,
+        count,
+        chain: AddressChain::Receive,
+      },
+      dice: None,
+    };
+    assert!(derive_wallet(params(0, MAX_ADDRESS_COUNT + 1)).is_err());
+    assert!(derive_wallet(params((1 << 31) - 1, 2)).is_err());
+    assert_eq!(
+      derive_wallet(params((1 << 31) - 1, 1))
+        .unwrap()
+        .addresses
+        .len(),
+      1
+    );
+  }
+}